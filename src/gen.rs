@@ -1,10 +1,16 @@
 use ::grid::Grid;
-use ::solver::Solver;
+use ::solver::{Solver, rate, strategy};
+use ::solver::rating::GUESS_WEIGHT;
+use ::solver::step::TECHNIQUES;
+pub use ::solver::Difficulty;
 
 use rand;
 use rand::{XorShiftRng,Rng};
 use rand::distributions::{Range,Sample};
 
+/// Number of puzzles `Generator::generate_rated` tries before giving up
+pub const RATED_ATTEMPTS: usize = 100;
+
 pub struct Generator {
     rng: XorShiftRng
 }
@@ -40,14 +46,70 @@ impl Generator {
                 continue;
             }
             vals[i] = -1;
-            let solver = Solver { allow_guessing: false };
-            if !solver.solve_values(&vals) {
+            if !Solver::with_strategies(strategy::basic()).solve_values(&vals).is_solved() {
                 return Grid::load(&prev);
             }
             prev = vals;
         }
     }
 
+    /// Generates a puzzle which requires techniques of exactly the given
+    /// `Difficulty` to solve
+    ///
+    /// Clues are removed for as long as the puzzle remains solvable at that
+    /// level, puzzles which turn out easier are discarded and retried
+    pub fn generate_with(&mut self, difficulty: Difficulty) -> Grid {
+        loop {
+            let vals = self.gen_values();
+            let mut order: [usize; 81] = [0; 81];
+            for (i, o) in order.iter_mut().enumerate() {
+                *o = i;
+            }
+            self.rng.shuffle(&mut order);
+
+            let mut puzzle = vals;
+            for &i in order.iter() {
+                let v = puzzle[i];
+                puzzle[i] = -1;
+                let keep = if difficulty == Difficulty::Diabolical {
+                    is_still_unique(&puzzle, i, v)
+                } else {
+                    solves_at(&puzzle, difficulty)
+                };
+                if !keep {
+                    puzzle[i] = v;
+                }
+            }
+
+            if required_difficulty(&puzzle) == difficulty {
+                return Grid::load(&puzzle);
+            }
+        }
+    }
+
+    /// Generates a puzzle with a `Rating` score between `min` and `max`
+    ///
+    /// Returns `None` if no technique scores within that range, or if none
+    /// of `RATED_ATTEMPTS` generated puzzles did; puzzles are rated by their
+    /// easiest steps, so some ranges are rarely or never hit
+    pub fn generate_rated(&mut self, min: f32, max: f32) -> Option<Grid> {
+        let levels: Vec<Difficulty> = Difficulty::all().iter().cloned()
+            .filter(|&d| can_score(d, min, max))
+            .collect();
+        if levels.is_empty() {
+            return None;
+        }
+        for _ in 0..RATED_ATTEMPTS {
+            let level = levels[self.rng.gen_range(0, levels.len())];
+            let grid = self.generate_with(level);
+            let score = rate(&grid).score;
+            if score >= min && score <= max {
+                return Some(grid);
+            }
+        }
+        None
+    }
+
     fn gen_values(&mut self) -> [i8; 81] {
         let mut order: [usize; 9] = [0,1,2,3,4,5,6,7,8];
 
@@ -57,9 +119,7 @@ impl Generator {
         for x in 0..9 {
             let xr = order[x] * 9;
             let xo = x * 9;
-            for y in 0..9 {
-                vals[xo + y] = BASE_GRID[xr + y];
-            }
+            vals[xo..xo + 9].copy_from_slice(&BASE_GRID[xr..xr + 9]);
         }
 
         self.shuffle(&mut order);
@@ -82,3 +142,36 @@ impl Generator {
         }
     }
 }
+
+/// Whether the puzzle can be solved without guessing using techniques up to
+/// the given `Difficulty`
+fn solves_at(values: &[i8; 81], difficulty: Difficulty) -> bool {
+    Solver::new(difficulty).solve_values(values).is_solved()
+}
+
+/// Easiest `Difficulty` at which the puzzle can be solved without guessing
+fn required_difficulty(values: &[i8; 81]) -> Difficulty {
+    Difficulty::all().iter().cloned()
+        .find(|&d| d != Difficulty::Diabolical && solves_at(values, d))
+        .unwrap_or(Difficulty::Diabolical)
+}
+
+/// Checks that a puzzle which had a unique solution is still unique after
+/// the clue `value` was removed from cell `i`
+///
+/// Any other solution must have a different value in that cell, so the
+/// puzzle is unique if no solution exists once `value` is excluded from it
+fn is_still_unique(values: &[i8; 81], i: usize, value: i8) -> bool {
+    let mut grid = Grid::load(values);
+    grid.values[i].possible &= !(1 << value);
+    !Solver::guessing().solve_mut(&mut grid.values).is_solved()
+}
+
+/// Whether puzzles at the given `Difficulty` can have their hardest step
+/// score between `min` and `max`
+fn can_score(difficulty: Difficulty, min: f32, max: f32) -> bool {
+    let in_range = |weight: f32| weight >= min && weight <= max;
+    (difficulty == Difficulty::Diabolical && in_range(GUESS_WEIGHT))
+        || TECHNIQUES.iter()
+            .any(|t| t.difficulty() == difficulty && in_range(t.weight()))
+}
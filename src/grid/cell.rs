@@ -1,5 +1,10 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::parse::{self, ParseError};
+
 /// A single cell in a `Grid`
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Cell {
     /// Value of the cell (0-8), -1 if unknown
     pub value: i8,
@@ -54,3 +59,33 @@ impl Cell {
         }
     }
 }
+
+impl fmt::Display for Cell {
+    /// Writes the value 1-9, or `.` if unknown
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.value == -1 {
+            f.write_str(".")
+        } else {
+            write!(f, "{}", self.value + 1)
+        }
+    }
+}
+
+impl FromStr for Cell {
+    type Err = ParseError;
+
+    /// Parses a single value 1-9, or `0` or `.` for an unknown cell with
+    /// every value possible
+    fn from_str(s: &str) -> Result<Cell, ParseError> {
+        let mut chars = s.chars();
+        let c = match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => return Err(ParseError::WrongLength { expected: 1, found: s.chars().count() })
+        };
+        match parse::value(c) {
+            Some(-1) => Ok(Cell { value: -1, possible: (1 << 9) - 1 }),
+            Some(v) => Ok(Cell { value: v, possible: 1 << v }),
+            None => Err(ParseError::InvalidCharacter { character: c, position: 0 })
+        }
+    }
+}
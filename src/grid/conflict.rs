@@ -0,0 +1,33 @@
+use std::fmt;
+
+use ::solver::Unit;
+use ::solver::step::cell_name;
+
+/// A mistake in a puzzle which means it has no solution
+///
+/// Cells are indices into the grid in row-major order, values are 0-8 as in
+/// `Cell::value`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Conflict {
+    /// The same value is given in two cells of a unit
+    Duplicate {
+        unit: Unit,
+        cells: (usize, usize),
+        value: i8
+    },
+    /// An unknown cell has no possible values left, each of them being in its
+    /// row, column or box already
+    NoCandidates(usize)
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Conflict::Duplicate { unit, cells: (a, b), value } => {
+                write!(f, "{} appears twice in {}: {} and {}",
+                       value + 1, unit, cell_name(a), cell_name(b))
+            },
+            Conflict::NoCandidates(i) => write!(f, "No value is possible in {}", cell_name(i))
+        }
+    }
+}
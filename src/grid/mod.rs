@@ -1,15 +1,21 @@
 mod cell;
+mod conflict;
+mod parse;
 pub use self::cell::Cell;
+pub use self::conflict::Conflict;
+pub use self::parse::ParseError;
 
-use ::solver::Solver;
+use std::fmt;
+use std::str::FromStr;
+
+use ::solver::{Solver, Trace, Unit};
+use ::solver::step::candidates;
 
 /// A 9x9 sudoku grid
-#[derive(Copy)]
+#[derive(Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Grid {
     /// Values in the grid in row-major order
     pub values: [Cell; 81],
-    /// Whether or not this is a valid puzzle
-    valid: bool,
     /// Whether or not this puzzle is solved
     pub solved: bool
 }
@@ -28,6 +34,8 @@ impl Grid {
     /// 0 or . implies the value is unknown
     /// If less than 81 values are found, the remainder are unknown
     /// Values after the 81st are ignored
+    ///
+    /// Use `try_parse` to reject input which isn't exactly one puzzle.
     pub fn parse(input: &str) -> Grid {
         let mut g = Grid::new();
         for (i, d) in input.chars()
@@ -43,6 +51,76 @@ impl Grid {
         g
     }
 
+    /// Parse a string containing exactly 81 cells into a `Grid`
+    ///
+    /// Values are read in row-major order
+    /// 0 or . implies the value is unknown
+    /// Whitespace and the characters `|`, `-` and `+` are ignored, so the
+    /// grid can be laid out in rows and boxes
+    /// Any other character is an error
+    pub fn try_parse(input: &str) -> Result<Grid, ParseError> {
+        let mut g = Grid::new();
+        let mut count = 0;
+        for (position, c) in input.chars().enumerate() {
+            if parse::is_separator(c) {
+                continue;
+            }
+            let v = match parse::value(c) {
+                Some(v) => v,
+                None => return Err(ParseError::InvalidCharacter { character: c, position })
+            };
+            if v != -1 && count < 81 {
+                g.values[count].value = v;
+                g.values[count].possible = 1 << v;
+            }
+            count += 1;
+        }
+        if count != 81 {
+            return Err(ParseError::WrongLength { expected: 81, found: count });
+        }
+        Ok(g)
+    }
+
+    /// Parse a pencilmark grid, with the possible values of each cell, into a
+    /// `Grid`
+    ///
+    /// Cells are read in row-major order as runs of the digits 1-9 possible
+    /// in them, a single digit being the cell's value
+    /// Cells are separated by whitespace or `|`
+    /// Box borders made of the characters `-+.:'=*`, as written by HoDoKu and
+    /// Sudoku Explainer, are ignored
+    /// Any other character is an error, as is finding other than 81 cells
+    pub fn parse_candidates(input: &str) -> Result<Grid, ParseError> {
+        let mut g = Grid::new();
+        let mut count = 0;
+        let mut position = 0;
+        for token in input.split(parse::is_cell_separator) {
+            if !parse::is_border(token) {
+                let mut possible = 0;
+                for (n, c) in token.chars().enumerate() {
+                    match c.to_digit(10) {
+                        Some(d) if d != 0 => possible |= 1 << (d - 1),
+                        _ => return Err(ParseError::InvalidCharacter {
+                            character: c,
+                            position: position + n
+                        })
+                    }
+                }
+                if count < 81 {
+                    g.values[count].possible = possible;
+                    g.values[count].check_possible();
+                }
+                count += 1;
+            }
+            // the token and the separator after it
+            position += token.chars().count() + 1;
+        }
+        if count != 81 {
+            return Err(ParseError::WrongLength { expected: 81, found: count });
+        }
+        Ok(g)
+    }
+
     pub fn load(values: &[i8; 81]) -> Grid {
         let mut g = Grid::new();
         for i in 0..81 {
@@ -61,23 +139,131 @@ impl Grid {
                 value: -1,
                 possible: (1 << 9) - 1
             }; 81],
-            valid: true,
             solved: false
         }
     }
 
+    /// Checks the puzzle for values given twice in a row, column or box, and
+    /// unknown cells with no possible values
+    ///
+    /// Returns every conflict found, duplicates being listed once for each
+    /// unit they are in
+    pub fn validate(&self) -> Result<(), Vec<Conflict>> {
+        let mut conflicts = Vec::new();
+        for &unit in Unit::all().iter() {
+            let cells = unit.cells();
+            for (n, &a) in cells.iter().enumerate() {
+                let value = self.values[a].value;
+                if value == -1 {
+                    continue;
+                }
+                for &b in cells[n + 1..].iter() {
+                    if self.values[b].value == value {
+                        conflicts.push(Conflict::Duplicate { unit, cells: (a, b), value });
+                    }
+                }
+            }
+        }
+        let cands = candidates(&self.values);
+        for i in 0..81 {
+            if self.values[i].value == -1 && cands[i] == 0 {
+                conflicts.push(Conflict::NoCandidates(i));
+            }
+        }
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(conflicts)
+        }
+    }
+
+    /// Counts the solutions of the puzzle, stopping once `limit` is reached
+    ///
+    /// Returns 0 if the puzzle is invalid
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        if self.validate().is_ok() {
+            Solver::guessing().count_solutions(&self.values, limit)
+        } else {
+            0
+        }
+    }
+
+    /// Returns `true` if the puzzle has exactly one solution
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
     /// Attempts to solve the puzzle
     ///
     /// Returns a filled out `Grid` when the solver has finished
     /// Returns `None` if the puzzle is invalid
     pub fn solve(&self) -> Option<Grid> {
-        if self.valid {
-            let mut g2 = self.clone();
-            let solver = Solver { allow_guessing: true };
-            if solver.solve_mut(&mut g2.values) {
+        if self.validate().is_ok() {
+            let mut g2 = *self;
+            if Solver::guessing().solve_mut(&mut g2.values).is_solved() {
                 return Some(g2);
             }
         }
         None
     }
+
+    /// Solves the puzzle one step at a time, guessing when no technique
+    /// applies
+    ///
+    /// Returns the filled out `Grid`, or `None` if the puzzle is invalid,
+    /// along with every deduction and guess made on the way
+    pub fn solve_traced(&self) -> (Option<Grid>, Trace) {
+        let mut trace = Trace::new();
+        if self.validate().is_ok() {
+            let mut g2 = *self;
+            if Solver::guessing().solve_traced(&mut g2.values, &mut trace) {
+                return (Some(g2), trace);
+            }
+        }
+        (None, trace)
+    }
+}
+
+impl FromStr for Grid {
+    type Err = ParseError;
+
+    /// Parses the grid with `Grid::try_parse`
+    fn from_str(s: &str) -> Result<Grid, ParseError> {
+        Grid::try_parse(s)
+    }
+}
+
+impl fmt::Display for Grid {
+    /// Writes the 81 values in row-major order with `.` for unknown cells,
+    /// or with `{:#}` a table of 9 rows with lines between the boxes and
+    /// blanks for unknown cells
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !f.alternate() {
+            for cell in self.values.iter() {
+                write!(f, "{}", cell)?;
+            }
+            return Ok(());
+        }
+        for (i, cell) in self.values.iter().enumerate() {
+            if i % 9 == 0 {
+                if i > 0 {
+                    if (i / 9) % 3 == 0 {
+                        f.write_str("\n------+-------+------\n")?;
+                    } else {
+                        f.write_str("\n")?;
+                    }
+                }
+            } else if i % 3 == 0 {
+                f.write_str(" | ")?;
+            } else {
+                f.write_str(" ")?;
+            }
+            if cell.value == -1 {
+                f.write_str(" ")?;
+            } else {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,60 @@
+use std::error::Error;
+use std::fmt;
+
+/// Reasons a puzzle can't be read by `Grid::try_parse` or
+/// `Grid::parse_candidates`, or a cell by `Cell::from_str`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// A character which is neither a value, a blank nor a separator, with
+    /// its index among the characters of the input
+    InvalidCharacter {
+        character: char,
+        position: usize
+    },
+    /// The input has the wrong number of cells
+    WrongLength {
+        expected: usize,
+        found: usize
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::InvalidCharacter { character, position } => {
+                write!(f, "Invalid character {:?} at position {}", character, position)
+            },
+            ParseError::WrongLength { expected, found } => {
+                write!(f, "Expected {} cells, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Value of a character as in `Cell::value`, -1 for a blank, or `None` if
+/// it isn't a value or a blank
+pub fn value(c: char) -> Option<i8> {
+    match c {
+        '.' => Some(-1),
+        _ => c.to_digit(10).map(|d| d as i8 - 1)
+    }
+}
+
+/// Whether the character only lays out the grid, such as the lines between
+/// boxes
+pub fn is_separator(c: char) -> bool {
+    c.is_whitespace() || c == '|' || c == '-' || c == '+'
+}
+
+/// Whether the character separates cells in a pencilmark grid
+pub fn is_cell_separator(c: char) -> bool {
+    c.is_whitespace() || c == '|'
+}
+
+/// Whether the token only draws the lines of a pencilmark grid, such as
+/// `.----+----.` or `:----+----:`
+pub fn is_border(token: &str) -> bool {
+    token.chars().all(|c| "-+.:'=*".contains(c))
+}
@@ -1,40 +1,32 @@
 use std::char;
-use sudoku::grid::{Grid, Cell};
 
-/// Generate 21x11 ascii table representing the `Grid`
-pub fn create(grid: &Grid) -> String {
-    let mut buf = String::with_capacity(241);
-    for (i, v) in grid.values.iter().enumerate() {
-        if (i % 9) == 0 {
-            if i > 0 {
-                if (i / 9) % 3 == 0 {
-                    buf.push_str("\n------+-------+------\n");
-                } else {
-                    buf.push('\n');
-                }
-            }
-        } else if i % 3 == 0 {
-            buf.push_str(" | ");
-        } else {
-            buf.push(' ');
-        }
+use ::grid::{Grid, Cell};
 
-        buf.push(number(v.value));
-    }
-    buf
+/// Turns a grid into text
+///
+/// Implemented for functions and closures taking a `&Grid`, so the
+/// renderers in this module can be used wherever a `Renderer` is expected.
+pub trait Renderer {
+    fn render(&self, grid: &Grid) -> String;
 }
-fn number(value: i8) -> char {
-    if value == -1 {
-        ' '
-    } else {
-        char::from_digit(value as u32 + 1, 10).unwrap()
+
+impl<F> Renderer for F where F: Fn(&Grid) -> String {
+    fn render(&self, grid: &Grid) -> String {
+        self(grid)
     }
 }
 
-/// Generate large ascii grid which displays cells' possible values
-pub fn create_large(grid: &Grid) -> String {
+/// A 21x11 table of the values with lines between the boxes, blank for
+/// unknown cells
+pub fn small(grid: &Grid) -> String {
+    format!("{:#}", grid)
+}
+
+/// A large grid drawing each known value as a figure, and the possible
+/// values of each unknown cell
+pub fn large(grid: &Grid) -> String {
     let mut printer = GridPrinter {
-        grid: grid,
+        grid,
         buf: String::with_capacity(2811)
     };
     for i in 0..9 {
@@ -45,7 +37,10 @@ pub fn create_large(grid: &Grid) -> String {
     printer.buf
 }
 
-pub fn create_oneline(grid: &Grid) -> String {
+/// The 81 values on one line in row-major order, blank for unknown cells
+///
+/// Unlike `Grid`'s `Display` this can't be read back with `Grid::parse`.
+pub fn oneline(grid: &Grid) -> String {
     let mut buf = String::with_capacity(82);
     for v in grid.values.iter() {
         buf.push(number(v.value));
@@ -53,6 +48,14 @@ pub fn create_oneline(grid: &Grid) -> String {
     buf
 }
 
+fn number(value: i8) -> char {
+    if value == -1 {
+        ' '
+    } else {
+        char::from_digit(value as u32 + 1, 10).unwrap()
+    }
+}
+
 struct GridPrinter<'a> {
     grid: &'a Grid,
     buf: String
@@ -83,7 +86,7 @@ impl<'a> GridPrinter<'a> {
                 self.buf.push(number(num));
             }
         } else {
-            self.buf.push_str(&FIG_CACHE[(r + cell.value as u32 * 3) as usize]);
+            self.buf.push_str(FIG_CACHE[(r + cell.value as u32 * 3) as usize]);
         }
         self.buf.push_str(" |");
     }
@@ -101,7 +104,7 @@ impl<'a> GridPrinter<'a> {
         }
     }
 }
-static FIG_CACHE: [&'static str; 27] = [
+static FIG_CACHE: [&str; 27] = [
     "   ,  ",
     "  /|  ",
     "   |  ",
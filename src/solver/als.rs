@@ -0,0 +1,262 @@
+use std::collections::HashSet;
+
+use super::step::{Step, Technique, Unit, PEERS, push_eliminated};
+
+/// An almost locked set: `n` unknown cells of a unit with `n + 1` possible
+/// values between them
+///
+/// If any one of its values is removed the rest are locked into its cells.
+pub struct Als {
+    pub unit: Unit,
+    pub cells: Vec<usize>,
+    /// Possible values of the cells as a bit field
+    pub values: u16,
+    /// Cells as a bit field over the grid
+    mask: u128,
+    /// Cells with each value, as bit fields over the grid
+    has: [u128; 9],
+    /// Cells seeing every cell with each value, as bit fields over the grid
+    seen: [u128; 9]
+}
+
+/// Finds every almost locked set in the rows, columns and boxes, a set of
+/// cells found in two units is only listed once
+pub fn almost_locked_sets(cands: &[u16; 81]) -> Vec<Als> {
+    let peers = peer_masks();
+    let mut found = Vec::new();
+    let mut masks = HashSet::new();
+    for &unit in Unit::all().iter() {
+        let free: Vec<usize> = unit.cells().iter().cloned()
+            .filter(|&i| cands[i] != 0)
+            .collect();
+        for set in 1..(1u32 << free.len()) {
+            let mut cells = Vec::new();
+            let mut values = 0;
+            for (n, &i) in free.iter().enumerate() {
+                if set & (1 << n) != 0 {
+                    cells.push(i);
+                    values |= cands[i];
+                }
+            }
+            if values.count_ones() as usize != cells.len() + 1 {
+                continue;
+            }
+            let mask = cells.iter().fold(0, |m, &i| m | 1 << i);
+            if !masks.insert(mask) {
+                continue;
+            }
+            let mut has = [0; 9];
+            let mut seen = [0; 9];
+            for v in 0..9 {
+                seen[v] = !0;
+                for &i in cells.iter().filter(|&&i| cands[i] & (1 << v) != 0) {
+                    has[v] |= 1 << i;
+                    seen[v] &= peers[i];
+                }
+                if has[v] == 0 {
+                    seen[v] = 0;
+                }
+            }
+            found.push(Als { unit, cells, values, mask, has, seen });
+        }
+    }
+    found
+}
+
+/// Find two almost locked sets A and B with a restricted common value X:
+/// every cell with X in A sees every cell with X in B, so X is only in one
+/// of them and the other is locked
+///
+/// Another value Z common to both must then be in A or B, and is removed
+/// from cells seeing all of their cells with Z. If they have two restricted
+/// common values both sets are locked, and their values are removed from
+/// cells seeing all of the set's cells with that value.
+pub fn xz(cands: &[u16; 81]) -> Option<Step> {
+    let sets = almost_locked_sets(cands);
+    let with = values_mask(cands);
+    for (n, a) in sets.iter().enumerate() {
+        for b in sets[n + 1..].iter() {
+            let rcc = restricted(a, b);
+            let outside = !(a.mask | b.mask);
+            let mut step = Step::new(Technique::AlsXz);
+            match rcc.count_ones() {
+                1 => {
+                    for z in 0..9 {
+                        if (a.values & b.values & !rcc) & (1 << z) != 0 {
+                            let targets = with[z] & a.seen[z] & b.seen[z] & outside;
+                            push_targets(&mut step, targets, z);
+                        }
+                    }
+                },
+                2 => {
+                    for v in 0..9 {
+                        let targets = if rcc & (1 << v) != 0 {
+                            with[v] & a.seen[v] & b.seen[v]
+                        } else {
+                            with[v] & (a.seen[v] | b.seen[v])
+                        };
+                        push_targets(&mut step, targets & outside, v);
+                    }
+                },
+                _ => continue
+            }
+            if !step.eliminated.is_empty() {
+                push_sets(&mut step, &[a, b]);
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// Find an almost locked set C with restricted common values X and Y to two
+/// other sets A and B; one of A or B is locked, so a value Z common to both
+/// is removed from cells seeing all of their cells with Z
+pub fn xy_wing(cands: &[u16; 81]) -> Option<Step> {
+    let sets = almost_locked_sets(cands);
+    let with = values_mask(cands);
+    for c in sets.iter() {
+        // sets linked to the pivot by restricted common values
+        let linked: Vec<(&Als, u16)> = sets.iter()
+            .map(|a| (a, restricted(a, c)))
+            .filter(|&(_, rcc)| rcc != 0)
+            .collect();
+        for (n, &(a, x)) in linked.iter().enumerate() {
+            for &(b, y) in linked[n + 1..].iter() {
+                // there must be two different restricted common values
+                if a.mask & b.mask != 0 || (x == y && x.count_ones() == 1) {
+                    continue;
+                }
+                let outside = !(a.mask | b.mask);
+                let mut step = Step::new(Technique::AlsXyWing);
+                for z in 0..9 {
+                    if (a.values & b.values & !(x | y)) & (1 << z) != 0 {
+                        let targets = with[z] & a.seen[z] & b.seen[z] & outside;
+                        push_targets(&mut step, targets, z);
+                    }
+                }
+                if !step.eliminated.is_empty() {
+                    push_sets(&mut step, &[a, b, c]);
+                    return Some(step);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Find a cell (the stem) with an almost locked set for each of its values
+/// (the petals), where all of a petal's cells with that value see the stem
+///
+/// Whichever value the stem takes, its petal is locked, so a value Z common
+/// to all petals is removed from cells seeing all of their cells with Z.
+pub fn death_blossom(cands: &[u16; 81]) -> Option<Step> {
+    let sets = almost_locked_sets(cands);
+    let with = values_mask(cands);
+    let peers = peer_masks();
+    for stem in 0..81 {
+        let count = cands[stem].count_ones();
+        if !(2..=3).contains(&count) {
+            continue;
+        }
+        let values: Vec<usize> = (0..9).filter(|&v| cands[stem] & (1 << v) != 0).collect();
+        for z in 0..9 {
+            if cands[stem] & (1 << z) != 0 {
+                continue;
+            }
+            // petals for each stem value which could remove z
+            let petals: Vec<Vec<&Als>> = values.iter().map(|&v| {
+                sets.iter()
+                    .filter(|a| a.mask & 1 << stem == 0 && a.has[v] != 0
+                        && a.has[v] & !peers[stem] == 0 && a.has[z] != 0)
+                    .collect()
+            }).collect();
+            let mut chosen = Vec::new();
+            let targets = with[z] & !(1 << stem);
+            if let Some(targets) = blossom(&petals, z, targets, 0, &mut chosen) {
+                let mut step = Step::new(Technique::DeathBlossom);
+                push_targets(&mut step, targets, z);
+                step.cells.push(stem);
+                push_sets(&mut step, &chosen);
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// Chooses a disjoint petal for each stem value from `petals[from..]`, keeping
+/// the cells with `z` which see all of their cells with `z`
+fn blossom<'a>(petals: &[Vec<&'a Als>], z: usize, targets: u128, from: usize,
+               chosen: &mut Vec<&'a Als>) -> Option<u128> {
+    if from == petals.len() {
+        return Some(targets);
+    }
+    let used = chosen.iter().fold(0, |m, a| m | a.mask);
+    for &a in petals[from].iter() {
+        let left = targets & a.seen[z] & !a.mask;
+        if a.mask & used != 0 || left == 0 {
+            continue;
+        }
+        chosen.push(a);
+        if let Some(found) = blossom(petals, z, left, from + 1, chosen) {
+            return Some(found);
+        }
+        chosen.pop();
+    }
+    None
+}
+
+/// Restricted common values of two disjoint sets as a bit field, values in
+/// both where every cell with it in one sees every cell with it in the other
+fn restricted(a: &Als, b: &Als) -> u16 {
+    if a.mask & b.mask != 0 {
+        return 0;
+    }
+    let mut rcc = 0;
+    for v in 0..9 {
+        if a.has[v] != 0 && b.has[v] != 0 && a.has[v] & !b.seen[v] == 0 {
+            rcc |= 1 << v;
+        }
+    }
+    rcc
+}
+
+/// Cells seen by each cell, as bit fields over the grid
+fn peer_masks() -> [u128; 81] {
+    let mut masks = [0; 81];
+    for (i, mask) in masks.iter_mut().enumerate() {
+        *mask = PEERS[i].iter().fold(0, |m, &p| m | 1 << p);
+    }
+    masks
+}
+
+/// Cells where each value is possible, as bit fields over the grid
+fn values_mask(cands: &[u16; 81]) -> [u128; 9] {
+    let mut masks = [0; 9];
+    for (v, mask) in masks.iter_mut().enumerate() {
+        for i in 0..81 {
+            if cands[i] & (1 << v) != 0 {
+                *mask |= 1 << i;
+            }
+        }
+    }
+    masks
+}
+
+/// Adds `v` as eliminated from every cell in the bit field `targets`
+fn push_targets(step: &mut Step, targets: u128, v: usize) {
+    for i in 0..81 {
+        if targets & 1 << i != 0 {
+            push_eliminated(step, i, 1 << v);
+        }
+    }
+}
+
+/// Adds the sets' units and cells to the step's pattern
+fn push_sets(step: &mut Step, sets: &[&Als]) {
+    for a in sets.iter() {
+        step.units.push(a.unit);
+        step.cells.extend(a.cells.iter().cloned());
+    }
+}
@@ -0,0 +1,393 @@
+use std::collections::VecDeque;
+
+use super::locked;
+use super::step::{Step, Technique, Unit, ChainNode, Link, PEERS, sees, units_of};
+
+/// A candidate, or a group of candidates for one value within a box-line
+/// intersection
+type Node = (Vec<usize>, i8);
+
+/// Find a loop of cells with a value, alternately joined by strong links
+/// (conjugate pairs) and weak links (cells seeing each other)
+///
+/// If the loop is continuous every weak link is also strong, and the value
+/// is removed from cells seeing both ends of any link (rule 1). If two
+/// strong links meet at a cell the value is placed there (rule 2), if two
+/// weak links meet it is removed (rule 3).
+pub fn x_cycle(cands: &[u16; 81]) -> Option<Step> {
+    for v in 0..9 {
+        let bit = 1 << v;
+        let strong = conjugates(cands, bit);
+        // weak links are followed from a true node, strong links from a
+        // false one; states are `cell * 2 + 1` if the value is true there
+        let next = |state: usize| -> Vec<usize> {
+            let i = state / 2;
+            if state % 2 == 1 {
+                PEERS[i].iter().filter(|&&j| cands[j] & bit != 0).map(|&j| j * 2).collect()
+            } else {
+                strong[i].iter().map(|&j| j * 2 + 1).collect()
+            }
+        };
+        for start in 0..81 {
+            if strong[start].is_empty() {
+                continue;
+            }
+            let from_false = search(start * 2, 162, &next);
+            // rule 2: false implies true, so the value is placed
+            if let Some(path) = path_to(&from_false, start * 2, start * 2 + 1) {
+                let mut step = cycle_step(&path[..path.len() - 1], v);
+                step.placed.push((start, v));
+                return Some(step);
+            }
+            // rule 1: the other end sees the start, closing a weak link
+            for &end in PEERS[start].iter() {
+                let path = match path_to(&from_false, start * 2, end * 2 + 1) {
+                    Some(path) => path,
+                    None => continue
+                };
+                let mut loop_cells: Vec<usize> = path.iter().map(|&s| s / 2).collect();
+                if loop_cells.len() < 4 {
+                    continue;
+                }
+                loop_cells.sort();
+                loop_cells.dedup();
+                if loop_cells.len() != path.len() {
+                    continue;
+                }
+                let mut step = cycle_step(&path, v);
+                for (n, &a) in path.iter().enumerate() {
+                    let b = path[(n + 1) % path.len()];
+                    for &i in PEERS[a / 2].iter() {
+                        if cands[i] & bit != 0 && !loop_cells.contains(&i) && sees(i, b / 2)
+                            && !step.eliminated.contains(&(i, v)) {
+                            step.eliminated.push((i, v));
+                        }
+                    }
+                }
+                if !step.eliminated.is_empty() {
+                    return Some(step);
+                }
+            }
+            // rule 3: true implies false, so the value is removed
+            let from_true = search(start * 2 + 1, 162, &next);
+            if let Some(path) = path_to(&from_true, start * 2 + 1, start * 2) {
+                let mut step = cycle_step(&path[..path.len() - 1], v);
+                step.eliminated.push((start, v));
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// Find a chain of cells with two possible values, each sharing a value
+/// with the next, where the first cell's value X being false makes the last
+/// cell's X true; X can be removed from cells seeing both ends
+pub fn xy_chain(cands: &[u16; 81]) -> Option<Step> {
+    // states are `cell * 9 + value` for the value which is true in the cell
+    let next = |state: usize| -> Vec<usize> {
+        let bit = 1 << (state % 9);
+        PEERS[state / 9].iter()
+            .filter(|&&j| cands[j].count_ones() == 2 && cands[j] & bit != 0)
+            .map(|&j| j * 9 + (cands[j] & !bit).trailing_zeros() as usize)
+            .collect()
+    };
+    for start in 0..81 {
+        if cands[start].count_ones() != 2 {
+            continue;
+        }
+        for x in 0..9 {
+            let bit = 1 << x;
+            if cands[start] & bit == 0 {
+                continue;
+            }
+            let y = (cands[start] & !bit).trailing_zeros() as usize;
+            let first = start * 9 + y;
+            let parents = search(first, 729, &next);
+            for end in 0..81 {
+                if end == start {
+                    continue;
+                }
+                let path = match path_to(&parents, first, end * 9 + x as usize) {
+                    Some(path) if path.len() >= 3 => path,
+                    _ => continue
+                };
+                let mut step = Step::new(Technique::XYChain);
+                for &i in PEERS[start].iter() {
+                    if i != end && cands[i] & bit != 0 && sees(i, end) {
+                        step.eliminated.push((i, x));
+                    }
+                }
+                if step.eliminated.is_empty() {
+                    continue;
+                }
+                for (n, &state) in path.iter().enumerate() {
+                    let (i, w) = (state / 9, (state % 9) as i8);
+                    let other = (cands[i] & !(1 << w)).trailing_zeros() as i8;
+                    step.cells.push(i);
+                    step.chain.push(ChainNode::new(i, other, Some(Link::Strong)));
+                    let link = if n == path.len() - 1 { None } else { Some(Link::Weak) };
+                    step.chain.push(ChainNode::new(i, w, link));
+                }
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// Find the shortest alternating inference chain: nodes joined alternately
+/// by strong links (if one is false the other is true) and weak links (if
+/// one is true the other is false), starting and ending with a strong link
+///
+/// Nodes are candidates, or groups of candidates for a value confined to the
+/// intersection of a box and a line. One of the end nodes must be true, so
+/// candidates which would make both false are removed.
+pub fn aic(cands: &[u16; 81]) -> Option<Step> {
+    let nodes = nodes(cands);
+    let (strong, weak) = links(cands, &nodes);
+    // states are `node * 2 + 1` if the node is true
+    let mut best = Vec::new();
+    let mut eliminated = Vec::new();
+    for start in 0..nodes.len() {
+        if strong[start].is_empty() {
+            continue;
+        }
+        let mut parents = vec![None; nodes.len() * 2];
+        let mut layer = vec![start * 2];
+        let mut length = 1;
+        // only look for chains shorter than the best so far
+        while !layer.is_empty() && (best.is_empty() || length + 1 < best.len()) {
+            length += 1;
+            let mut next = Vec::new();
+            for &state in layer.iter() {
+                let (n, on) = (state / 2, state % 2 == 1);
+                for &m in if on { &weak[n] } else { &strong[n] }.iter() {
+                    let reached = m * 2 + if on { 0 } else { 1 };
+                    if reached == start * 2 || parents[reached].is_some() {
+                        continue;
+                    }
+                    parents[reached] = Some(state);
+                    next.push(reached);
+                }
+            }
+            if length >= 4 && length % 2 == 0 {
+                let found = next.iter().find_map(|&state| {
+                    let found = chain_eliminations(cands, &nodes[start], &nodes[state / 2]);
+                    let path = path_to(&parents, start * 2, state)?;
+                    if found.is_empty() || !is_simple(&nodes, &path) {
+                        None
+                    } else {
+                        Some((path, found))
+                    }
+                });
+                if let Some((path, found)) = found {
+                    best = path;
+                    eliminated = found;
+                    break;
+                }
+            }
+            layer = next;
+        }
+    }
+    if best.is_empty() {
+        return None;
+    }
+
+    let grouped = best.iter().any(|&state| nodes[state / 2].0.len() > 1);
+    let mut step = Step::new(if grouped { Technique::GroupedAic } else { Technique::Aic });
+    for (k, &state) in best.iter().enumerate() {
+        let (ref cells, v) = nodes[state / 2];
+        let link = if k == best.len() - 1 {
+            None
+        } else if state % 2 == 0 {
+            Some(Link::Strong)
+        } else {
+            Some(Link::Weak)
+        };
+        step.cells.extend(cells.iter().cloned());
+        step.chain.push(ChainNode { cells: cells.clone(), value: v, link });
+    }
+    step.cells.sort();
+    step.cells.dedup();
+    step.eliminated = eliminated;
+    Some(step)
+}
+
+/// Cells joined to each cell by a conjugate pair of the value
+fn conjugates(cands: &[u16; 81], bit: u16) -> Vec<Vec<usize>> {
+    let mut links: Vec<Vec<usize>> = vec![Vec::new(); 81];
+    for &unit in Unit::all().iter() {
+        let cells: Vec<usize> = unit.cells().iter().cloned()
+            .filter(|&i| cands[i] & bit != 0)
+            .collect();
+        if cells.len() == 2 && !links[cells[0]].contains(&cells[1]) {
+            links[cells[0]].push(cells[1]);
+            links[cells[1]].push(cells[0]);
+        }
+    }
+    links
+}
+
+/// Every candidate, followed by the groups of two or three candidates of a
+/// value within a box-line intersection
+fn nodes(cands: &[u16; 81]) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    for i in 0..81 {
+        for v in 0..9 {
+            if cands[i] & (1 << v) != 0 {
+                nodes.push((vec![i], v));
+            }
+        }
+    }
+    for &(_, _, cells) in locked::intersections().iter() {
+        for v in 0..9 {
+            let group: Vec<usize> = cells.iter().cloned()
+                .filter(|&i| cands[i] & (1 << v) != 0)
+                .collect();
+            if group.len() > 1 {
+                nodes.push((group, v));
+            }
+        }
+    }
+    nodes
+}
+
+/// Strong and weak links from each node, strong links also being weak
+fn links(cands: &[u16; 81], nodes: &[Node]) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
+    let mut strong = vec![Vec::new(); nodes.len()];
+    let mut weak = vec![Vec::new(); nodes.len()];
+    for (a, &(ref cells_a, v)) in nodes.iter().enumerate() {
+        for (b, &(ref cells_b, w)) in nodes.iter().enumerate().skip(a + 1) {
+            let (is_weak, is_strong) = if v == w {
+                if cells_a.iter().any(|i| cells_b.contains(i)) {
+                    continue;
+                }
+                let cells: Vec<usize> = cells_a.iter().chain(cells_b.iter()).cloned().collect();
+                let units = common_units(&cells);
+                // both nodes hold every place for the value in a unit
+                let conjugate = units.iter().any(|unit| {
+                    unit.cells().iter().filter(|&&i| cands[i] & (1 << v) != 0).count()
+                        == cells.len()
+                });
+                (!units.is_empty(), conjugate)
+            } else if cells_a.len() == 1 && cells_a == cells_b {
+                (true, cands[cells_a[0]].count_ones() == 2)
+            } else {
+                continue;
+            };
+            if is_weak {
+                weak[a].push(b);
+                weak[b].push(a);
+            }
+            if is_strong {
+                strong[a].push(b);
+                strong[b].push(a);
+            }
+        }
+    }
+    (strong, weak)
+}
+
+/// Whether no two nodes of a chain overlap
+fn is_simple(nodes: &[Node], path: &[usize]) -> bool {
+    path.iter().enumerate().all(|(k, &a)| {
+        let (ref cells_a, v) = nodes[a / 2];
+        path[k + 1..].iter().all(|&b| {
+            let (ref cells_b, w) = nodes[b / 2];
+            v != w || !cells_a.iter().any(|i| cells_b.contains(i))
+        })
+    })
+}
+
+/// Units containing all of the cells
+fn common_units(cells: &[usize]) -> Vec<Unit> {
+    let (row, col, bx) = units_of(cells[0]);
+    let mut units = Vec::new();
+    if cells.iter().all(|&i| units_of(i).0 == row) {
+        units.push(Unit::Row(row));
+    }
+    if cells.iter().all(|&i| units_of(i).1 == col) {
+        units.push(Unit::Column(col));
+    }
+    if cells.iter().all(|&i| units_of(i).2 == bx) {
+        units.push(Unit::Box(bx));
+    }
+    units
+}
+
+/// Candidates removed because the `start` or `end` node of a chain is true
+fn chain_eliminations(cands: &[u16; 81], start: &Node, end: &Node) -> Vec<(usize, i8)> {
+    let (ref cells_a, x) = *start;
+    let (ref cells_b, y) = *end;
+    let mut eliminated = Vec::new();
+    if x == y {
+        for i in 0..81 {
+            if cands[i] & (1 << x) != 0
+                && !cells_a.contains(&i) && !cells_b.contains(&i)
+                && cells_a.iter().chain(cells_b.iter()).all(|&j| sees(i, j)) {
+                eliminated.push((i, x));
+            }
+        }
+    } else if cells_a.len() == 1 && cells_a == cells_b {
+        // the cell is one of the two values
+        let i = cells_a[0];
+        for v in 0..9 {
+            if v != x && v != y && cands[i] & (1 << v) != 0 {
+                eliminated.push((i, v));
+            }
+        }
+    } else {
+        // a single end removes the other end's value if it sees all of it
+        for &(single, other, v) in [(cells_b, cells_a, x), (cells_a, cells_b, y)].iter() {
+            let i = single[0];
+            if single.len() == 1 && cands[i] & (1 << v) != 0
+                && !other.contains(&i) && other.iter().all(|&j| sees(i, j)) {
+                eliminated.push((i, v));
+            }
+        }
+    }
+    eliminated
+}
+
+/// Breadth first search over `size` implication states from `start`,
+/// returning the state each one was first reached from
+fn search(start: usize, size: usize, next: &dyn Fn(usize) -> Vec<usize>) -> Vec<Option<usize>> {
+    let mut parents = vec![None; size];
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+    while let Some(state) = queue.pop_front() {
+        for m in next(state) {
+            if m != start && parents[m].is_none() {
+                parents[m] = Some(state);
+                queue.push_back(m);
+            }
+        }
+    }
+    parents
+}
+
+/// States along the shortest path from `start` to `end`, if reached
+fn path_to(parents: &[Option<usize>], start: usize, end: usize) -> Option<Vec<usize>> {
+    let mut path = vec![end];
+    let mut state = end;
+    while state != start {
+        state = parents[state]?;
+        path.push(state);
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// An X-Cycle step for the loop through the states in `path`, the last
+/// linking back to the first
+fn cycle_step(path: &[usize], v: i8) -> Step {
+    let mut step = Step::new(Technique::XCycle);
+    for &state in path.iter() {
+        // a false node is followed by a strong link, a true one by a weak link
+        let link = if state % 2 == 0 { Link::Strong } else { Link::Weak };
+        step.cells.push(state / 2);
+        step.chain.push(ChainNode::new(state / 2, v, Some(link)));
+    }
+    step
+}
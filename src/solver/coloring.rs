@@ -0,0 +1,144 @@
+use super::step::{Step, Technique, Unit, sees};
+
+/// Cells of a chain of conjugate pairs, split by color
+type Chain = [Vec<usize>; 2];
+
+/// Find a chain of conjugate pairs of a value (units in which it is only
+/// possible in two cells), colored alternately
+///
+/// If two cells of the same color see each other that color is false and
+/// the value is removed from all of its cells (color wrap). Otherwise the
+/// value is removed from cells seeing both colors (color trap).
+pub fn simple(cands: &[u16; 81]) -> Option<Step> {
+    for v in 0..9 {
+        let chains = chains(cands, 1 << v);
+        for chain in chains.iter() {
+            for c in 0..2 {
+                if sees_any(&chain[c], &chain[c]) {
+                    let mut step = coloring_step(Technique::SimpleColoring, v, &[chain]);
+                    step.eliminated = chain[c].iter().map(|&i| (i, v)).collect();
+                    return Some(step);
+                }
+            }
+        }
+        for chain in chains.iter() {
+            let mut step = coloring_step(Technique::SimpleColoring, v, &[chain]);
+            for i in 0..81 {
+                if cands[i] & (1 << v) != 0
+                    && !chain[0].contains(&i) && !chain[1].contains(&i)
+                    && sees_any(&[i], &chain[0]) && sees_any(&[i], &chain[1]) {
+                    step.eliminated.push((i, v));
+                }
+            }
+            if !step.eliminated.is_empty() {
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// Find two chains of conjugate pairs of the same value where a color of
+/// one sees a color of the other, so one of the opposite colors must be true
+///
+/// The value is removed from cells seeing both opposite colors, or from all
+/// cells of a color which sees both colors of the other chain.
+pub fn multi(cands: &[u16; 81]) -> Option<Step> {
+    for v in 0..9 {
+        let bit = 1 << v;
+        let chains = chains(cands, bit);
+        for (n, a) in chains.iter().enumerate() {
+            for (m, b) in chains.iter().enumerate() {
+                if m == n {
+                    continue;
+                }
+                for ca in 0..2 {
+                    // a color seeing both colors of the other chain is false
+                    if sees_any(&a[ca], &b[0]) && sees_any(&a[ca], &b[1]) {
+                        let mut step = coloring_step(Technique::MultiColoring, v, &[a, b]);
+                        step.eliminated = a[ca].iter().map(|&i| (i, v)).collect();
+                        return Some(step);
+                    }
+                }
+            }
+            for b in chains[n + 1..].iter() {
+                for ca in 0..2 {
+                    for cb in 0..2 {
+                        if !sees_any(&a[ca], &b[cb]) {
+                            continue;
+                        }
+                        // one of the opposite colors is true
+                        let (oa, ob) = (&a[1 - ca], &b[1 - cb]);
+                        let mut step = coloring_step(Technique::MultiColoring, v, &[a, b]);
+                        for i in 0..81 {
+                            if cands[i] & bit != 0
+                                && !step.cells.contains(&i)
+                                && sees_any(&[i], oa) && sees_any(&[i], ob) {
+                                step.eliminated.push((i, v));
+                            }
+                        }
+                        if !step.eliminated.is_empty() {
+                            return Some(step);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Splits the conjugate pairs of a value into connected chains and colors
+/// them
+fn chains(cands: &[u16; 81], bit: u16) -> Vec<Chain> {
+    // cells joined to each cell by a conjugate pair
+    let mut links: Vec<Vec<usize>> = vec![Vec::new(); 81];
+    for &unit in Unit::all().iter() {
+        let cells: Vec<usize> = unit.cells().iter().cloned()
+            .filter(|&i| cands[i] & bit != 0)
+            .collect();
+        if cells.len() == 2 && !links[cells[0]].contains(&cells[1]) {
+            links[cells[0]].push(cells[1]);
+            links[cells[1]].push(cells[0]);
+        }
+    }
+
+    let mut colored = [false; 81];
+    let mut chains = Vec::new();
+    for start in 0..81 {
+        if colored[start] || links[start].is_empty() {
+            continue;
+        }
+        let mut chain: Chain = [Vec::new(), Vec::new()];
+        let mut stack = vec![(start, 0)];
+        colored[start] = true;
+        while let Some((i, c)) = stack.pop() {
+            chain[c].push(i);
+            for &j in links[i].iter() {
+                if !colored[j] {
+                    colored[j] = true;
+                    stack.push((j, 1 - c));
+                }
+            }
+        }
+        chains.push(chain);
+    }
+    chains
+}
+
+/// Whether any cell in `a` sees any cell in `b`
+fn sees_any(a: &[usize], b: &[usize]) -> bool {
+    a.iter().any(|&i| b.iter().any(|&j| sees(i, j)))
+}
+
+/// A step with the given chains as its pattern and colors
+fn coloring_step(technique: Technique, v: i8, chains: &[&Chain]) -> Step {
+    let mut step = Step::new(technique);
+    for chain in chains.iter() {
+        for color in chain.iter() {
+            step.cells.extend(color.iter().cloned());
+            step.colors.push(color.iter().map(|&i| (i, v)).collect());
+        }
+    }
+    step
+}
@@ -0,0 +1,227 @@
+use super::step::{Step, Technique, Unit, units_of};
+
+/// Find two rows (or columns) in which a value is only possible in the same
+/// two columns (or rows), so it can be removed from the rest of those
+#[inline]
+pub fn x_wing(cands: &[u16; 81]) -> Option<Step> {
+    find(cands, 2, Technique::XWing)
+}
+
+/// Like `x_wing`, with three rows and columns
+#[inline]
+pub fn swordfish(cands: &[u16; 81]) -> Option<Step> {
+    find(cands, 3, Technique::Swordfish)
+}
+
+/// Like `x_wing`, with four rows and columns
+#[inline]
+pub fn jellyfish(cands: &[u16; 81]) -> Option<Step> {
+    find(cands, 4, Technique::Jellyfish)
+}
+
+/// Find an X-Wing with extra candidates (fins) in one of its base lines, all
+/// within one box
+///
+/// The value can only be removed from cells in the cover lines which are
+/// also in the fins' box. Reported as sashimi if a base line has just one
+/// candidate left without its fins.
+#[inline]
+pub fn finned_x_wing(cands: &[u16; 81]) -> Option<Step> {
+    find_finned(cands, 2, Technique::FinnedXWing, Technique::SashimiXWing)
+}
+
+/// Like `finned_x_wing`, with three rows and columns
+#[inline]
+pub fn finned_swordfish(cands: &[u16; 81]) -> Option<Step> {
+    find_finned(cands, 3, Technique::FinnedSwordfish, Technique::SashimiSwordfish)
+}
+
+/// Constructor of a row or column `Unit`
+type Line = fn(usize) -> Unit;
+
+/// Find a fish with `size` base lines, trying rows first then columns
+fn find(cands: &[u16; 81], size: u32, technique: Technique) -> Option<Step> {
+    let orientations: [(Line, Line); 2] = [
+        (Unit::Row, Unit::Column),
+        (Unit::Column, Unit::Row)
+    ];
+    for &(base, cover) in orientations.iter() {
+        for v in 0..9 {
+            let bit = 1 << v;
+            // positions of the value within each base line
+            let mut lines: [u16; 9] = [0; 9];
+            // bit is 1 for base lines which can take part in the fish
+            let mut eligible: u16 = 0;
+            for l in 0..9 {
+                for p in 0..9 {
+                    if cands[base(l).cell(p)] & bit != 0 {
+                        lines[l] |= 1 << p;
+                    }
+                }
+                if (2..=size).contains(&lines[l].count_ones()) {
+                    eligible |= 1 << l;
+                }
+            }
+            for set in 0..(1 << 9) as u16 {
+                if set & !eligible != 0 || set.count_ones() != size {
+                    continue;
+                }
+                let mut covers = 0;
+                for l in 0..9 {
+                    if set & (1 << l) != 0 {
+                        covers |= lines[l];
+                    }
+                }
+                if covers.count_ones() != size {
+                    continue;
+                }
+
+                let mut step = Step::new(technique);
+                for p in 0..9 {
+                    if covers & (1 << p) == 0 {
+                        continue;
+                    }
+                    for l in 0..9 {
+                        let i = base(l).cell(p);
+                        if set & (1 << l) == 0 && cands[i] & bit != 0 {
+                            step.eliminated.push((i, v));
+                        }
+                    }
+                }
+                if step.eliminated.is_empty() {
+                    continue;
+                }
+                for l in 0..9 {
+                    if set & (1 << l) != 0 {
+                        step.units.push(base(l));
+                        for p in 0..9 {
+                            if lines[l] & (1 << p) != 0 {
+                                step.cells.push(base(l).cell(p));
+                            }
+                        }
+                    }
+                }
+                for p in 0..9 {
+                    if covers & (1 << p) != 0 {
+                        step.units.push(cover(p));
+                    }
+                }
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// Find a finned fish with `size` base lines, trying rows first then columns
+fn find_finned(cands: &[u16; 81], size: u32,
+               finned: Technique, sashimi: Technique) -> Option<Step> {
+    let orientations: [(Line, Line); 2] = [
+        (Unit::Row, Unit::Column),
+        (Unit::Column, Unit::Row)
+    ];
+    for &(base, cover) in orientations.iter() {
+        for v in 0..9 {
+            let bit = 1 << v;
+            let mut lines: [u16; 9] = [0; 9];
+            let mut eligible: u16 = 0;
+            for l in 0..9 {
+                for p in 0..9 {
+                    if cands[base(l).cell(p)] & bit != 0 {
+                        lines[l] |= 1 << p;
+                    }
+                }
+                if lines[l] != 0 {
+                    eligible |= 1 << l;
+                }
+            }
+            for set in 0..(1 << 9) as u16 {
+                if set & !eligible != 0 || set.count_ones() != size {
+                    continue;
+                }
+                let mut all = 0;
+                for l in 0..9 {
+                    if set & (1 << l) != 0 {
+                        all |= lines[l];
+                    }
+                }
+                for covers in 0..(1 << 9) as u16 {
+                    if covers & !all != 0 || covers.count_ones() != size {
+                        continue;
+                    }
+                    if let Some(step) = try_finned(cands, v, base, cover, &lines,
+                                                   set, covers, finned, sashimi) {
+                        return Some(step);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Checks the base lines in `set` and cover lines in `covers` for a finned
+/// fish on value `v`
+#[allow(clippy::too_many_arguments)]
+fn try_finned(cands: &[u16; 81], v: i8, base: Line, cover: Line, lines: &[u16; 9],
+              set: u16, covers: u16,
+              finned: Technique, sashimi: Technique) -> Option<Step> {
+    let mut fin_box = None;
+    let mut is_sashimi = false;
+    for l in 0..9 {
+        if set & (1 << l) == 0 {
+            continue;
+        }
+        match (lines[l] & covers).count_ones() {
+            0 => return None,
+            1 => is_sashimi = true,
+            _ => {}
+        }
+        let fins = lines[l] & !covers;
+        for p in 0..9 {
+            if fins & (1 << p) != 0 {
+                let (_, _, bx) = units_of(base(l).cell(p));
+                match fin_box {
+                    Some(b) if b != bx => return None,
+                    _ => fin_box = Some(bx)
+                }
+            }
+        }
+    }
+    // without fins it would be a basic fish
+    let fin_box = fin_box?;
+
+    let mut step = Step::new(if is_sashimi { sashimi } else { finned });
+    for p in 0..9 {
+        if covers & (1 << p) == 0 {
+            continue;
+        }
+        for l in 0..9 {
+            let i = base(l).cell(p);
+            if set & (1 << l) == 0 && cands[i] & (1 << v) != 0
+                && units_of(i).2 == fin_box {
+                step.eliminated.push((i, v));
+            }
+        }
+    }
+    if step.eliminated.is_empty() {
+        return None;
+    }
+    for l in 0..9 {
+        if set & (1 << l) != 0 {
+            step.units.push(base(l));
+            for p in 0..9 {
+                if lines[l] & (1 << p) != 0 {
+                    step.cells.push(base(l).cell(p));
+                }
+            }
+        }
+    }
+    for p in 0..9 {
+        if covers & (1 << p) != 0 {
+            step.units.push(cover(p));
+        }
+    }
+    step.units.push(Unit::Box(fin_box));
+    Some(step)
+}
@@ -0,0 +1,181 @@
+use super::step::{Step, Technique, Unit, PEERS};
+
+/// Most rounds of singles followed from an assumption before giving up
+pub const MAX_DEPTH: usize = 12;
+
+/// Candidates left after assuming a value and following singles from it
+struct Branch {
+    cands: [u16; 81],
+    /// Cells whose value is known in this branch, as a bit field
+    placed: u128
+}
+
+/// Find a candidate which leads to a contradiction when assumed true and
+/// followed through naked and hidden singles; it can be removed
+pub fn nishio(cands: &[u16; 81]) -> Option<Step> {
+    let needed = needed(cands);
+    for i in 0..81 {
+        for v in 0..9 {
+            if cands[i] & (1 << v) != 0 && assume(cands, &needed, i, v).is_none() {
+                let mut step = Step::new(Technique::Nishio);
+                step.cells.push(i);
+                step.eliminated.push((i, v));
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// Find a cell where every possible value, assumed true and followed through
+/// singles, leads to the same placement or removal; it can be made
+pub fn cell(cands: &[u16; 81]) -> Option<Step> {
+    let needed = needed(cands);
+    for i in 0..81 {
+        if cands[i].count_ones() < 2 {
+            continue;
+        }
+        let sources: Vec<(usize, i8)> = (0..9)
+            .filter(|&v| cands[i] & (1 << v) != 0)
+            .map(|v| (i, v))
+            .collect();
+        if let Some(mut step) = common(cands, &needed, &sources, Technique::CellForcingChain) {
+            step.cells.push(i);
+            return Some(step);
+        }
+    }
+    None
+}
+
+/// Find a value in a unit where every cell it is possible in, assumed to
+/// hold it and followed through singles, leads to the same placement or
+/// removal; it can be made
+pub fn unit(cands: &[u16; 81]) -> Option<Step> {
+    let needed = needed(cands);
+    for &unit in Unit::all().iter() {
+        for v in 0..9 {
+            let sources: Vec<(usize, i8)> = unit.cells().iter().cloned()
+                .filter(|&i| cands[i] & (1 << v) != 0)
+                .map(|i| (i, v))
+                .collect();
+            if sources.len() < 2 {
+                continue;
+            }
+            if let Some(mut step) = common(cands, &needed, &sources, Technique::UnitForcingChain) {
+                step.units.push(unit);
+                step.cells = sources.iter().map(|&(i, _)| i).collect();
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// Values which still have to be placed in each unit, indexed as in
+/// `Unit::all`
+fn needed(cands: &[u16; 81]) -> [u16; 27] {
+    let mut needed = [0; 27];
+    for (n, unit) in Unit::all().iter().enumerate() {
+        needed[n] = unit.cells().iter().fold(0, |m, &i| m | cands[i]);
+    }
+    needed
+}
+
+/// A step for the placements, or failing that the removals, shared by all
+/// of the branches from `sources` which don't lead to a contradiction
+fn common(cands: &[u16; 81], needed: &[u16; 27], sources: &[(usize, i8)],
+          technique: Technique) -> Option<Step> {
+    let branches: Vec<Branch> = sources.iter()
+        .filter_map(|&(i, v)| assume(cands, needed, i, v))
+        .collect();
+    if branches.is_empty() {
+        return None;
+    }
+    let mut step = Step::new(technique);
+    for j in 0..81 {
+        if cands[j].count_ones() < 2 {
+            continue;
+        }
+        let first = branches[0].cands[j];
+        let placed = branches.iter().all(|b| b.placed & 1 << j != 0 && b.cands[j] == first);
+        if placed {
+            step.placed.push((j, first.trailing_zeros() as i8));
+        }
+    }
+    if step.placed.is_empty() {
+        for j in 0..81 {
+            let removed = branches.iter().fold(cands[j], |m, b| m & !b.cands[j]);
+            for v in 0..9 {
+                if removed & (1 << v) != 0 {
+                    step.eliminated.push((j, v));
+                }
+            }
+        }
+    }
+    if step.placed.is_empty() && step.eliminated.is_empty() {
+        None
+    } else {
+        Some(step)
+    }
+}
+
+/// Follows naked and hidden singles from placing `v` in cell `i`, for at
+/// most `MAX_DEPTH` rounds; `None` if it leads to a contradiction
+fn assume(cands: &[u16; 81], needed: &[u16; 27], i: usize, v: i8) -> Option<Branch> {
+    let mut branch = Branch { cands: *cands, placed: 0 };
+    let mut queue = vec![(i, v)];
+    for _ in 0..MAX_DEPTH {
+        if queue.is_empty() {
+            break;
+        }
+        for (j, w) in queue.drain(..) {
+            if !place(&mut branch, j, w) {
+                return None;
+            }
+        }
+        for j in 0..81 {
+            if cands[j] == 0 || branch.placed & 1 << j != 0 {
+                continue;
+            }
+            match branch.cands[j].count_ones() {
+                0 => return None,
+                1 => queue.push((j, branch.cands[j].trailing_zeros() as i8)),
+                _ => {}
+            }
+        }
+        for (n, unit) in Unit::all().iter().enumerate() {
+            for w in 0..9 {
+                if needed[n] & (1 << w) == 0 {
+                    continue;
+                }
+                let cells: Vec<usize> = unit.cells().iter().cloned()
+                    .filter(|&j| branch.cands[j] & (1 << w) != 0)
+                    .collect();
+                match cells.len() {
+                    0 => return None,
+                    1 if branch.placed & 1 << cells[0] == 0 => queue.push((cells[0], w)),
+                    _ => {}
+                }
+            }
+        }
+    }
+    Some(branch)
+}
+
+/// Places `v` in cell `i` of the branch and removes it from the cell's
+/// peers, returns `false` on a contradiction
+fn place(branch: &mut Branch, i: usize, v: i8) -> bool {
+    let bit = 1 << v;
+    if branch.cands[i] & bit == 0 {
+        return false;
+    }
+    branch.cands[i] = bit;
+    branch.placed |= 1 << i;
+    for &p in PEERS[i].iter() {
+        if branch.placed & 1 << p != 0 && branch.cands[p] == bit {
+            return false;
+        }
+        branch.cands[p] &= !bit;
+    }
+    true
+}
@@ -1,5 +1,6 @@
 use super::*;
 use ::grid::Cell;
+use super::step::{Step, Technique, Unit, push_eliminated};
 
 macro_rules! run {
     ($cells:ident, $func:ident) => {{
@@ -31,7 +32,7 @@ macro_rules! run {
                     if p != 0 {
                         // cell has unique possible
                         let i = $func(major, minor);
-                        let mut cell = &mut $cells[i];
+                        let cell = &mut $cells[i];
                         cell.possible = p;
                         changed |= cell.check_possible();
                     }
@@ -54,3 +55,94 @@ pub fn columns(cells: &mut [Cell;  81]) -> bool {
 pub fn boxes(cells: &mut [Cell;  81]) -> bool {
     run!(cells, box_loc)
 }
+
+/// Find a value which is possible in only one cell of a row, column or box
+pub fn find(cands: &[u16; 81]) -> Option<Step> {
+    for &unit in Unit::all().iter() {
+        for v in 0..9 {
+            let bit = 1 << v;
+            let mut found = None;
+            let mut count = 0;
+            for &i in unit.cells().iter() {
+                if cands[i] & bit != 0 {
+                    found = Some(i);
+                    count += 1;
+                }
+            }
+            if let (1, Some(i)) = (count, found) {
+                let mut step = Step::new(Technique::HiddenSingle);
+                step.units.push(unit);
+                step.cells.push(i);
+                step.placed.push((i, v));
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// Find two values which are only possible in the same two cells of a unit,
+/// so other values can be removed from those cells
+#[inline]
+pub fn find_pair(cands: &[u16; 81]) -> Option<Step> {
+    find_subset(cands, 2, Technique::HiddenPair)
+}
+
+/// Like `find_pair`, with three values confined to three cells
+#[inline]
+pub fn find_triple(cands: &[u16; 81]) -> Option<Step> {
+    find_subset(cands, 3, Technique::HiddenTriple)
+}
+
+/// Like `find_pair`, with four values confined to four cells
+#[inline]
+pub fn find_quad(cands: &[u16; 81]) -> Option<Step> {
+    find_subset(cands, 4, Technique::HiddenQuad)
+}
+
+/// Find `size` values in a unit which are only possible in `size` cells
+fn find_subset(cands: &[u16; 81], size: u32, technique: Technique) -> Option<Step> {
+    for &unit in Unit::all().iter() {
+        let cells = unit.cells();
+        // cells in which each value is possible
+        let mut places: [u16; 9] = [0; 9];
+        // bit is 1 for values which can take part in the subset
+        let mut eligible: u16 = 0;
+        for v in 0..9 {
+            for (minor, &i) in cells.iter().enumerate() {
+                if cands[i] & (1 << v) != 0 {
+                    places[v] |= 1 << minor;
+                }
+            }
+            if (2..=size).contains(&places[v].count_ones()) {
+                eligible |= 1 << v;
+            }
+        }
+        for values in 0..(1 << 9) as u16 {
+            if values & !eligible != 0 || values.count_ones() != size {
+                continue;
+            }
+            let mut set = 0;
+            for v in 0..9 {
+                if values & (1 << v) != 0 {
+                    set |= places[v];
+                }
+            }
+            if set.count_ones() != size {
+                continue;
+            }
+            let mut step = Step::new(technique);
+            for (minor, &i) in cells.iter().enumerate() {
+                if set & (1 << minor) != 0 {
+                    push_eliminated(&mut step, i, cands[i] & !values);
+                    step.cells.push(i);
+                }
+            }
+            if !step.eliminated.is_empty() {
+                step.units.push(unit);
+                return Some(step);
+            }
+        }
+    }
+    None
+}
@@ -1,5 +1,6 @@
 use super::*;
 use ::grid::Cell;
+use super::step::{Step, Technique, Unit, units_of, push_eliminated};
 
 macro_rules! run {
     ($cells:ident, $outer_func:ident, $inner_func:ident) => {{
@@ -35,7 +36,7 @@ macro_rules! run {
                             for minor in 0..3 {
                                 // 3 $cells in each inner segment
                                 let i = outer_offset + $inner_func(major, minor);
-                                let mut cell = &mut $cells[i];
+                                let cell = &mut $cells[i];
                                 if cell.value == -1
                                     && (cell.possible & uniq) != 0 {
                                         cell.possible &= !uniq;
@@ -68,3 +69,155 @@ pub fn box_rows(cells: &mut [Cell;  81]) -> bool {
 pub fn box_cols(cells: &mut [Cell;  81]) -> bool {
     run!(cells, col_loc, inv_box_loc)
 }
+
+/// Find a value confined to one row or column within a box, which can be
+/// removed from the rest of that row or column
+pub fn pointing(cands: &[u16; 81]) -> Option<Step> {
+    find(cands, &Unit::all()[..9])
+}
+
+/// Find a value confined to one box within a row or column, which can be
+/// removed from the rest of that box
+pub fn claiming(cands: &[u16; 81]) -> Option<Step> {
+    find(cands, &Unit::all()[9..])
+}
+
+/// Find a value confined to the intersection of one of the units with another
+fn find(cands: &[u16; 81], units: &[Unit]) -> Option<Step> {
+    for &unit in units.iter() {
+        for v in 0..9 {
+            let bit = 1 << v;
+            let cells: Vec<usize> = unit.cells().iter().cloned()
+                .filter(|&i| cands[i] & bit != 0)
+                .collect();
+            if cells.len() < 2 {
+                continue;
+            }
+            let (row, col, bx) = units_of(cells[0]);
+            let same_row = cells.iter().all(|&i| units_of(i).0 == row);
+            let same_col = cells.iter().all(|&i| units_of(i).1 == col);
+            let same_box = cells.iter().all(|&i| units_of(i).2 == bx);
+            let (technique, other) = match unit {
+                Unit::Box(_) if same_row => (Technique::Pointing, Unit::Row(row)),
+                Unit::Box(_) if same_col => (Technique::Pointing, Unit::Column(col)),
+                Unit::Box(_) => continue,
+                _ if same_box => (Technique::Claiming, Unit::Box(bx)),
+                _ => continue
+            };
+            let mut step = Step::new(technique);
+            for &i in other.cells().iter() {
+                if !cells.contains(&i) {
+                    push_eliminated(&mut step, i, cands[i] & bit);
+                }
+            }
+            if !step.eliminated.is_empty() {
+                step.units.push(unit);
+                step.units.push(other);
+                step.cells = cells;
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// The 54 intersections of a box with a row or column, as the box, the line
+/// and the three cells they share
+pub fn intersections() -> Vec<(Unit, Unit, [usize; 3])> {
+    let mut found = Vec::with_capacity(54);
+    for bx in 0..9 {
+        for k in 0..3 {
+            let row = [box_loc(bx, k * 3), box_loc(bx, k * 3 + 1), box_loc(bx, k * 3 + 2)];
+            found.push((Unit::Box(bx), Unit::Row(bx / 3 * 3 + k), row));
+            let col = [box_loc(bx, k), box_loc(bx, k + 3), box_loc(bx, k + 6)];
+            found.push((Unit::Box(bx), Unit::Column(bx % 3 * 3 + k), col));
+        }
+    }
+    found
+}
+
+/// Find two or three cells in a box-line intersection with at least two more
+/// possible values than cells, and cells elsewhere in the line and in the
+/// box which together with them hold as many values as cells, with no value
+/// in both the line cells and the box cells (Sue de Coq)
+///
+/// Every value is then locked into either the line or the box, and is
+/// removed from the rest of that unit.
+pub fn sue_de_coq(cands: &[u16; 81]) -> Option<Step> {
+    for &(bx, line, ref shared) in intersections().iter() {
+        let core: Vec<usize> = shared.iter().cloned().filter(|&i| cands[i] != 0).collect();
+        let line_rest = rest(cands, line, shared);
+        let box_rest = rest(cands, bx, shared);
+        let line_sets = subsets(cands, &line_rest);
+        let box_sets = subsets(cands, &box_rest);
+        for set in 1..(1u32 << core.len()) {
+            let size = set.count_ones() as usize;
+            if size < 2 {
+                continue;
+            }
+            let cells: Vec<usize> = (0..core.len())
+                .filter(|&n| set & (1 << n) != 0)
+                .map(|n| core[n])
+                .collect();
+            let values = cells.iter().fold(0, |m, &i| m | cands[i]);
+            if (values.count_ones() as usize) < size + 2 {
+                continue;
+            }
+            for &(line_set, line_values) in line_sets.iter() {
+                for &(box_set, box_values) in box_sets.iter() {
+                    let all = values | line_values | box_values;
+                    let count = size + (line_set.count_ones() + box_set.count_ones()) as usize;
+                    if line_values & box_values != 0 || all.count_ones() as usize != count {
+                        continue;
+                    }
+                    let mut step = Step::new(Technique::SueDeCoq);
+                    // values in the box cells can't be in the rest of the line
+                    for &(rest, others, used) in [(&line_rest, box_values, line_set),
+                                                  (&box_rest, line_values, box_set)].iter() {
+                        for (n, &i) in rest.iter().enumerate() {
+                            if used & (1 << n) == 0 {
+                                push_eliminated(&mut step, i, cands[i] & all & !others);
+                            }
+                        }
+                    }
+                    if !step.eliminated.is_empty() {
+                        step.units = vec![bx, line];
+                        step.cells = cells;
+                        for (n, &i) in line_rest.iter().enumerate() {
+                            if line_set & (1 << n) != 0 {
+                                step.cells.push(i);
+                            }
+                        }
+                        for (n, &i) in box_rest.iter().enumerate() {
+                            if box_set & (1 << n) != 0 {
+                                step.cells.push(i);
+                            }
+                        }
+                        return Some(step);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Unknown cells of the unit outside the intersection
+fn rest(cands: &[u16; 81], unit: Unit, shared: &[usize; 3]) -> Vec<usize> {
+    unit.cells().iter().cloned()
+        .filter(|&i| cands[i] != 0 && !shared.contains(&i))
+        .collect()
+}
+
+/// Every non-empty subset of the cells as a bit field over their indices,
+/// with their possible values
+fn subsets(cands: &[u16; 81], cells: &[usize]) -> Vec<(u32, u16)> {
+    (1..(1u32 << cells.len()))
+        .map(|set| {
+            let values = (0..cells.len())
+                .filter(|&n| set & (1 << n) != 0)
+                .fold(0, |m, n| m | cands[cells[n]]);
+            (set, values)
+        })
+        .collect()
+}
@@ -0,0 +1,133 @@
+use super::step::{Step, Technique, Unit, PEERS, sees};
+
+/// Find a cluster of candidates joined by strong links, within bivalue cells
+/// and between conjugate pairs of every value, colored alternately
+///
+/// A color is false if two of its candidates share a cell or a unit, or if
+/// it would leave another cell with no candidates, and all of its
+/// candidates are removed. Otherwise candidates are removed which see both
+/// colors, share a cell with one color and see the other, or share a cell
+/// with both colors.
+pub fn find(cands: &[u16; 81]) -> Option<Step> {
+    let links = links(cands);
+    let mut color: [Option<(usize, usize)>; 729] = [None; 729];
+    let mut clusters = 0;
+    for start in 0..729 {
+        if color[start].is_some() || links[start].is_empty() {
+            continue;
+        }
+        let mut stack = vec![(start, 0)];
+        color[start] = Some((clusters, 0));
+        while let Some((n, c)) = stack.pop() {
+            for &m in links[n].iter() {
+                if color[m].is_none() {
+                    color[m] = Some((clusters, 1 - c));
+                    stack.push((m, 1 - c));
+                }
+            }
+        }
+        clusters += 1;
+    }
+
+    for cluster in 0..clusters {
+        let mut groups: [Vec<(usize, i8)>; 2] = [Vec::new(), Vec::new()];
+        for n in 0..729 {
+            if let Some((k, c)) = color[n] {
+                if k == cluster {
+                    groups[c].push((n / 9, (n % 9) as i8));
+                }
+            }
+        }
+        let colored = |i: usize, v: i8| match color[i * 9 + v as usize] {
+            Some((k, c)) if k == cluster => Some(c),
+            _ => None
+        };
+        let mut step = Step::new(Technique::Medusa);
+        step.cells = groups[0].iter().chain(groups[1].iter()).map(|&(i, _)| i).collect();
+        step.cells.sort();
+        step.cells.dedup();
+        step.colors = groups.to_vec();
+
+        for c in 0..2 {
+            if is_false(cands, &groups[c], &|i, v| colored(i, v) == Some(c)) {
+                step.eliminated = groups[c].clone();
+                return Some(step);
+            }
+        }
+
+        for i in 0..81 {
+            for v in 0..9 {
+                if cands[i] & (1 << v) == 0 || colored(i, v).is_some() {
+                    continue;
+                }
+                let seen = |c: usize| groups[c].iter()
+                    .any(|&(j, w)| w == v && sees(i, j));
+                let in_cell = |c: usize| (0..9)
+                    .any(|w| cands[i] & (1 << w) != 0 && colored(i, w) == Some(c));
+                let both_seen = seen(0) && seen(1);
+                let cell_and_seen = (in_cell(0) && seen(1)) || (in_cell(1) && seen(0));
+                let cell_both = in_cell(0) && in_cell(1);
+                if both_seen || cell_and_seen || cell_both {
+                    step.eliminated.push((i, v));
+                }
+            }
+        }
+        if !step.eliminated.is_empty() {
+            return Some(step);
+        }
+    }
+    None
+}
+
+/// Whether a color is contradictory: two of its candidates in one cell, the
+/// same value twice in a unit, or a cell whose candidates are all seen by it
+fn is_false(cands: &[u16; 81], group: &[(usize, i8)], has: &dyn Fn(usize, i8) -> bool) -> bool {
+    for (n, &(i, v)) in group.iter().enumerate() {
+        for &(j, w) in group[n + 1..].iter() {
+            if (i == j && v != w) || (v == w && sees(i, j)) {
+                return true;
+            }
+        }
+    }
+    for i in 0..81 {
+        if cands[i] == 0 || (0..9).any(|v| has(i, v)) {
+            continue;
+        }
+        let emptied = (0..9)
+            .filter(|&v| cands[i] & (1 << v) != 0)
+            .all(|v| PEERS[i].iter().any(|&j| has(j, v)));
+        if emptied {
+            return true;
+        }
+    }
+    false
+}
+
+/// Strong links between candidates, indexed by `cell * 9 + value`
+fn links(cands: &[u16; 81]) -> Vec<Vec<usize>> {
+    let mut links: Vec<Vec<usize>> = vec![Vec::new(); 729];
+    let mut add = |a: usize, b: usize| {
+        if !links[a].contains(&b) {
+            links[a].push(b);
+            links[b].push(a);
+        }
+    };
+    for i in 0..81 {
+        if cands[i].count_ones() == 2 {
+            let v = cands[i].trailing_zeros() as usize;
+            let w = 15 - cands[i].leading_zeros() as usize;
+            add(i * 9 + v, i * 9 + w);
+        }
+    }
+    for &unit in Unit::all().iter() {
+        for v in 0..9 {
+            let cells: Vec<usize> = unit.cells().iter().cloned()
+                .filter(|&i| cands[i] & (1 << v) != 0)
+                .collect();
+            if cells.len() == 2 {
+                add(cells[0] * 9 + v, cells[1] * 9 + v);
+            }
+        }
+    }
+    links
+}
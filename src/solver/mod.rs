@@ -2,8 +2,26 @@ pub mod simple;
 pub mod hidden;
 pub mod locked;
 pub mod naked;
+pub mod fish;
+pub mod wings;
+pub mod single_digit;
+pub mod coloring;
+pub mod medusa;
+pub mod chains;
+pub mod unique;
+pub mod als;
+pub mod forcing;
+pub mod strategy;
+pub mod step;
+pub mod trace;
+pub mod rating;
 
-use ::grid::Cell;
+pub use self::step::{Step, Technique, Unit};
+pub use self::trace::{Trace, Event};
+pub use self::rating::{Rating, rate};
+pub use self::strategy::{Strategy, StepResult};
+
+use ::grid::{Grid, Cell};
 
 #[inline]
 pub fn row_loc(major: usize, minor: usize) -> usize {
@@ -41,14 +59,14 @@ macro_rules! check_pass {
             if cell.value == -1 {
                 if cell.possible == 0 {
                     // no possible values
-                    return false;
+                    return State::Invalid;
                 }
                 $done = false;
             } else {
                 let val_bit = 1 << cell.value;
                 if ($vals[$id] & val_bit) != 0 {
                     // duplicate value
-                    return false;
+                    return State::Invalid;
                 }
                 $vals[$id] |= val_bit;
             }
@@ -56,12 +74,116 @@ macro_rules! check_pass {
     }}
 }
 
+/// Difficulty levels, each allowing the techniques of all levels below it
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    /// Only removes values already present in the row, column or box
+    Easy,
+    /// Adds hidden singles
+    Medium,
+    /// Adds locked candidates (pointing and claiming)
+    Hard,
+    /// Adds naked pairs and triples, hidden pairs and X-Wings
+    Expert,
+    /// Adds larger subsets, swordfish, jellyfish, finned fish, wings,
+    /// single digit patterns, coloring, chains, almost locked sets and
+    /// forcing chains, or requires guessing
+    Diabolical
+}
+
+impl Difficulty {
+    /// All difficulty levels from easiest to hardest
+    pub fn all() -> [Difficulty; 5] {
+        [
+            Difficulty::Easy,
+            Difficulty::Medium,
+            Difficulty::Hard,
+            Difficulty::Expert,
+            Difficulty::Diabolical
+        ]
+    }
+}
+
+/// Outcome of solving a puzzle
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Every cell has a value
+    Solved,
+    /// No allowed strategy applies, with the possible values left in each
+    /// cell when the solver stopped as returned by `step::candidates`
+    Stuck([u16; 81]),
+    /// A cell has no possible values or a value is repeated in a unit, so
+    /// the puzzle has no solution
+    Contradiction
+}
+
+impl Outcome {
+    /// Returns `true` if the puzzle was solved
+    pub fn is_solved(&self) -> bool {
+        matches!(*self, Outcome::Solved)
+    }
+}
+
+/// Outcome of applying the logical techniques until no more progress is made
+enum State {
+    Solved,
+    Stuck,
+    Invalid
+}
+
+/// Solves puzzles by applying an ordered list of strategies
 pub struct Solver {
-    pub allow_guessing: bool
+    /// Whether to guess when no strategy applies
+    pub allow_guessing: bool,
+    /// Whether to use strategies which rely on the puzzle having a unique
+    /// solution, such as unique rectangles; these can remove values which
+    /// are part of a solution when it has several
+    pub assume_unique: bool,
+    /// Strategies in the order they are tried, after any of them makes
+    /// progress the solver applies the basic strategies following it and
+    /// starts again from the first
+    pub strategies: Vec<Box<dyn Strategy>>
 }
 
 impl Solver {
-    pub fn solve_values(&self, values: &[i8; 81]) -> bool {
+    /// A solver using the built-in techniques up to `max_difficulty`, which
+    /// doesn't guess or assume uniqueness
+    pub fn new(max_difficulty: Difficulty) -> Solver {
+        Solver::with_strategies(strategy::builtin(max_difficulty))
+    }
+
+    /// A solver using the given strategies, which doesn't guess or assume
+    /// uniqueness
+    pub fn with_strategies(strategies: Vec<Box<dyn Strategy>>) -> Solver {
+        Solver {
+            allow_guessing: false,
+            assume_unique: false,
+            strategies
+        }
+    }
+
+    /// A solver using only the given built-in techniques, which doesn't guess
+    /// or assume uniqueness
+    ///
+    /// For example a puzzle which can be solved using only singles:
+    /// `Solver::with_techniques(&[Technique::NakedSingle, Technique::HiddenSingle])`
+    pub fn with_techniques(techniques: &[Technique]) -> Solver {
+        Solver::with_strategies(strategy::techniques(techniques))
+    }
+
+    /// The quickest solver for finding solutions, using only the basic
+    /// techniques before guessing
+    ///
+    /// Guessing is quicker than searching for the advanced techniques.
+    pub fn guessing() -> Solver {
+        Solver {
+            allow_guessing: true,
+            ..Solver::with_strategies(strategy::basic())
+        }
+    }
+
+    /// Solves the puzzle given by its values, -1 where unknown
+    pub fn solve_values(&self, values: &[i8; 81]) -> Outcome {
         let mut cells = [Cell {
             value: -1,
             possible: (1 << 9) - 1
@@ -76,78 +198,219 @@ impl Solver {
         self.solve_mut(&mut cells)
     }
 
-    /// Solve the puzzle in place, returns `true` if successful
-    pub fn solve_mut(&self, cells: &mut [Cell; 81]) -> bool {
+    /// Solve the puzzle in place
+    ///
+    /// Only returns `Outcome::Stuck` when guessing isn't allowed, otherwise
+    /// the puzzle is either solved or has no solution.
+    pub fn solve_mut(&self, cells: &mut [Cell; 81]) -> Outcome {
+        match self.reduce(cells, self.assume_unique) {
+            State::Solved => Outcome::Solved,
+            State::Stuck if self.allow_guessing => self.guess(cells),
+            State::Stuck => Outcome::Stuck(step::candidates(cells)),
+            State::Invalid => Outcome::Contradiction
+        }
+    }
+
+    /// Counts the solutions of the puzzle, stopping once `limit` is reached
+    ///
+    /// Unlike `solve_mut` the search continues past the first solution,
+    /// it always guesses when stuck regardless of `allow_guessing` and never
+    /// assumes uniqueness
+    pub fn count_solutions(&self, cells: &[Cell; 81], limit: usize) -> usize {
+        if limit == 0 {
+            return 0;
+        }
+        let mut cells = *cells;
+        match self.reduce(&mut cells, false) {
+            State::Solved => 1,
+            State::Invalid => 0,
+            State::Stuck => {
+                let c = match guess_cell(&cells) {
+                    Some(c) => c,
+                    None => return 0
+                };
+                let mut count = 0;
+                for v in 0..9 {
+                    if cells[c].is_possible(v) {
+                        let mut new_cells = cells;
+                        {
+                            let cell = &mut new_cells[c];
+                            cell.value = v;
+                            cell.possible = 1 << v;
+                        }
+                        count += self.count_solutions(&new_cells, limit - count);
+                        if count >= limit {
+                            break;
+                        }
+                    }
+                }
+                count
+            }
+        }
+    }
+
+    /// Returns `true` if the puzzle has exactly one solution
+    pub fn has_unique_solution(&self, cells: &[Cell; 81]) -> bool {
+        self.count_solutions(cells, 2) == 1
+    }
+
+    /// Finds the next deduction in the grid, trying the easiest techniques
+    /// first
+    ///
+    /// Returns `None` if no strategy applies, e.g. when the grid is already
+    /// solved. Use `Step::apply` to make the
+    /// deduction.
+    pub fn next_step(&self, grid: &Grid) -> Option<Step> {
+        self.find_step(&grid.values)
+    }
+
+    /// Solves the puzzle in place one step at a time, recording every
+    /// deduction and guess in `trace`
+    ///
+    /// Returns `true` if successful
+    pub fn solve_traced(&self, cells: &mut [Cell; 81], trace: &mut Trace) -> bool {
+        step::prune(cells);
+        self.trace_mut(cells, trace, 0)
+    }
+
+    fn find_step(&self, cells: &[Cell; 81]) -> Option<Step> {
+        let cands = step::candidates(cells);
+        self.allowed(self.assume_unique)
+            .filter_map(|strategy| strategy.find(&cands))
+            .next()
+    }
+
+    /// Strategies the solver may use, in the order they are tried
+    fn allowed<'a>(&'a self, assume_unique: bool) -> impl Iterator<Item = &'a dyn Strategy> + 'a {
+        self.strategies.iter()
+            .map(|strategy| strategy.as_ref())
+            .filter(move |strategy| assume_unique || !strategy.assumes_unique())
+    }
+
+    /// Applies the strategies until the puzzle is solved, found to be
+    /// invalid or no more progress can be made
+    fn reduce(&self, cells: &mut [Cell; 81], assume_unique: bool) -> State {
+        for cell in cells.iter_mut() {
+            cell.check_possible();
+        }
+        // without this hidden singles and the like would work on values
+        // already placed in a peer
+        let prune = !self.allowed(assume_unique).any(|strategy| strategy.prunes_peers());
         loop {
+            if prune {
+                step::prune(cells);
+            }
             let mut changed = false;
-            changed |= simple::rows(cells);
-            changed |= simple::columns(cells);
-            changed |= simple::boxes(cells);
-            changed |= hidden::rows(cells);
-            changed |= hidden::columns(cells);
-            changed |= hidden::boxes(cells);
-            changed |= locked::rows(cells);
-            changed |= locked::columns(cells);
-            changed |= locked::box_rows(cells);
-            changed |= locked::box_cols(cells);
-            changed |= naked::rows(cells);
-            changed |= naked::columns(cells);
-            changed |= naked::boxes(cells);
-
-            // check if grid is solved or invalid
-            let mut done = true;
-            for major in 0..9 {
-                let mut vals: [u16; 3] = [0; 3];
-                for minor in 0..9 {
-                    check_pass!(cells,
-                        [row_loc:0, col_loc:1, box_loc:2](major, minor)
-                        -> vals, done);
+            for strategy in self.allowed(assume_unique) {
+                if changed && !strategy.is_basic() {
+                    break;
                 }
+                changed |= strategy.apply(cells) == StepResult::Changed;
             }
-            if done {
-                return true;
-            } else if !changed {
-                return if self.allow_guessing {
-                    self.guess(cells)
-                } else {
-                    false
+
+            match check(cells) {
+                State::Stuck if changed => {},
+                state => return state
+            }
+        }
+    }
+
+    fn trace_mut(&self, cells: &mut [Cell; 81], trace: &mut Trace, depth: usize) -> bool {
+        loop {
+            match check(cells) {
+                State::Solved => return true,
+                State::Invalid => return false,
+                State::Stuck => {}
+            }
+            match self.find_step(cells) {
+                Some(step) => {
+                    step.apply(cells);
+                    trace.push_step(step);
                 }
+                None => break
             }
         }
+        if !self.allow_guessing {
+            return false;
+        }
+
+        let c = match guess_cell(cells) {
+            Some(c) => c,
+            None => return false
+        };
+        for v in 0..9 {
+            if cells[c].is_possible(v) {
+                let guess = trace.push_guess(c, v, depth + 1);
+                let mut new_cells = *cells;
+                step::place(&mut new_cells, c, v);
+                if self.trace_mut(&mut new_cells, trace, depth + 1) {
+                    *cells = new_cells;
+                    return true;
+                }
+                trace.backtrack(guess);
+            }
+        }
+        false
     }
 
     #[inline]
-    fn guess(&self, cells: &mut [Cell; 81]) -> bool  {
-        for poss in 2..10 { // find cells with least possibles first
-            for c in 0..81 {
-                let cell = cells[c];
-                if cell.value == -1 {
-                    let mut p = cell.possible;
-                    let mut v = 0;
-                    if p.count_ones() == poss {
-                        while p != 0 {
-                            // check next LSB of possibles
-                            if p & 1 != 0 {
-                                let mut new_cells = *cells;
-                                {
-                                    let mut cell = &mut new_cells[c];
-                                    cell.value = v as i8;
-                                    cell.possible = 1 << v;
-                                }
-                                if self.solve_mut(&mut new_cells) {
-                                    *cells = new_cells;
-                                    return true;
-                                }
-                            }
-                            // remove LSB from possibles
-                            p = p >> 1;
-                            v += 1;
-                        }
-                        return false;
-                    }
+    fn guess(&self, cells: &mut [Cell; 81]) -> Outcome {
+        let c = match guess_cell(cells) {
+            Some(c) => c,
+            None => return Outcome::Stuck(step::candidates(cells))
+        };
+        let mut p = cells[c].possible;
+        let mut v = 0;
+        while p != 0 {
+            // check next LSB of possibles
+            if p & 1 != 0 {
+                let mut new_cells = *cells;
+                {
+                    let cell = &mut new_cells[c];
+                    cell.value = v as i8;
+                    cell.possible = 1 << v;
+                }
+                if self.solve_mut(&mut new_cells).is_solved() {
+                    *cells = new_cells;
+                    return Outcome::Solved;
                 }
             }
+            // remove LSB from possibles
+            p >>= 1;
+            v += 1;
+        }
+        Outcome::Contradiction
+    }
+}
+
+/// Checks if the grid is solved or invalid, `Stuck` means neither
+fn check(cells: &[Cell; 81]) -> State {
+    let mut done = true;
+    for major in 0..9 {
+        let mut vals: [u16; 3] = [0; 3];
+        for minor in 0..9 {
+            check_pass!(cells,
+                [row_loc:0, col_loc:1, box_loc:2](major, minor)
+                -> vals, done);
+        }
+    }
+    if done {
+        State::Solved
+    } else {
+        State::Stuck
+    }
+}
+
+/// Finds the unknown cell with the fewest possible values to guess on
+#[inline]
+fn guess_cell(cells: &[Cell; 81]) -> Option<usize> {
+    for poss in 2..10 { // find cells with least possibles first
+        for c in 0..81 {
+            let cell = cells[c];
+            if cell.value == -1 && cell.possible.count_ones() == poss {
+                return Some(c);
+            }
         }
-        false
     }
+    None
 }
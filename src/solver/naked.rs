@@ -1,5 +1,6 @@
 use super::*;
 use ::grid::Cell;
+use super::step::{Step, Technique, Unit, push_eliminated};
 
 macro_rules! run {
     ($cells:ident, $func:ident) => {{
@@ -16,7 +17,7 @@ macro_rules! run {
                 }
             }
             if count < 3 {
-                break;
+                continue;
             }
             'outer: for a in 0..count - 1 {
                 for b in a + 1..count {
@@ -28,7 +29,7 @@ macro_rules! run {
                             if o_poss != 0
                                 && o != a && o != b
                                     && (o_poss & a_poss) != 0 {
-                                        let mut cell = &mut $cells[i];
+                                        let cell = &mut $cells[i];
                                         cell.possible &= !a_poss;
                                         cell.check_possible();
                                         changed = true;
@@ -56,3 +57,66 @@ pub fn columns(cells: &mut [Cell;  81]) -> bool {
 pub fn boxes(cells: &mut [Cell;  81]) -> bool {
     run!(cells, box_loc)
 }
+
+/// Find two cells in a unit with the same two possible values, which can be
+/// removed from the rest of the unit
+#[inline]
+pub fn find(cands: &[u16; 81]) -> Option<Step> {
+    find_subset(cands, 2, Technique::NakedPair)
+}
+
+/// Like `find`, with three cells sharing three possible values
+#[inline]
+pub fn find_triple(cands: &[u16; 81]) -> Option<Step> {
+    find_subset(cands, 3, Technique::NakedTriple)
+}
+
+/// Like `find`, with four cells sharing four possible values
+#[inline]
+pub fn find_quad(cands: &[u16; 81]) -> Option<Step> {
+    find_subset(cands, 4, Technique::NakedQuad)
+}
+
+/// Find `size` cells in a unit whose possible values together number `size`
+fn find_subset(cands: &[u16; 81], size: u32, technique: Technique) -> Option<Step> {
+    for &unit in Unit::all().iter() {
+        let cells = unit.cells();
+        // bit is 1 for cells which can take part in the subset
+        let mut eligible: u16 = 0;
+        for (minor, &i) in cells.iter().enumerate() {
+            if (2..=size).contains(&cands[i].count_ones()) {
+                eligible |= 1 << minor;
+            }
+        }
+        for set in 0..(1 << 9) as u16 {
+            if set & !eligible != 0 || set.count_ones() != size {
+                continue;
+            }
+            let mut values = 0;
+            for (minor, &i) in cells.iter().enumerate() {
+                if set & (1 << minor) != 0 {
+                    values |= cands[i];
+                }
+            }
+            if values.count_ones() != size {
+                continue;
+            }
+            let mut step = Step::new(technique);
+            for (minor, &i) in cells.iter().enumerate() {
+                if set & (1 << minor) == 0 {
+                    push_eliminated(&mut step, i, cands[i] & values);
+                }
+            }
+            if !step.eliminated.is_empty() {
+                step.units.push(unit);
+                for (minor, &i) in cells.iter().enumerate() {
+                    if set & (1 << minor) != 0 {
+                        step.cells.push(i);
+                    }
+                }
+                return Some(step);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,106 @@
+use super::*;
+use super::step::{prune, place};
+use ::grid::Grid;
+
+/// Weight of a guess, above that of any technique
+pub const GUESS_WEIGHT: f32 = 12.0;
+
+/// Difficulty rating of a puzzle, see `rate`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rating {
+    /// Weight of the hardest step
+    pub score: f32,
+    /// Technique used in the hardest step, `None` if it was a guess or no
+    /// steps were needed
+    pub hardest: Option<Technique>,
+    /// Sum of the weights of all steps
+    pub total: f32,
+    /// Number of steps, including guesses
+    pub steps: usize,
+    /// Number of guesses
+    pub guesses: usize,
+    /// Whether the puzzle was solved, `false` if it is invalid
+    pub solved: bool
+}
+
+impl Rating {
+    fn add(&mut self, weight: f32, technique: Option<Technique>) {
+        if weight > self.score {
+            self.score = weight;
+            self.hardest = technique;
+        }
+        self.total += weight;
+        self.steps += 1;
+    }
+}
+
+/// Rates the difficulty of a puzzle in the style of Sudoku Explainer
+///
+/// The puzzle is solved using the lowest weighted technique that applies at
+/// each step, the score is the weight of the hardest step. When no technique
+/// applies the correct value is placed as a guess worth `GUESS_WEIGHT`.
+/// Uniqueness techniques are only used if the puzzle has a unique solution.
+pub fn rate(grid: &Grid) -> Rating {
+    let solver = Solver {
+        assume_unique: true,
+        ..Solver::new(Difficulty::Diabolical)
+    };
+    solver.rate(grid)
+}
+
+impl Solver {
+    /// Rates the difficulty of a puzzle like `rate`, using the solver's
+    /// strategies and weights
+    ///
+    /// Strategies which rely on a unique solution are only used if
+    /// `assume_unique` is set and the puzzle has one.
+    pub fn rate(&self, grid: &Grid) -> Rating {
+        let mut rating = Rating {
+            score: 0.0,
+            hardest: None,
+            total: 0.0,
+            steps: 0,
+            guesses: 0,
+            solved: false
+        };
+        let solution = match grid.solve() {
+            Some(solution) => solution,
+            None => return rating
+        };
+        let assume_unique = self.assume_unique && grid.has_unique_solution();
+
+        let mut cells = grid.values;
+        prune(&mut cells);
+        loop {
+            match check(&cells) {
+                State::Solved => {
+                    rating.solved = true;
+                    return rating;
+                },
+                State::Invalid => return rating,
+                State::Stuck => {}
+            }
+            let cands = step::candidates(&cells);
+            let easiest = self.allowed(assume_unique)
+                .filter_map(|strategy| strategy.find(&cands))
+                .min_by(|a, b| a.technique.weight()
+                    .partial_cmp(&b.technique.weight())
+                    .unwrap());
+            match easiest {
+                Some(step) => {
+                    rating.add(step.technique.weight(), Some(step.technique));
+                    step.apply(&mut cells);
+                },
+                None => {
+                    let c = match guess_cell(&cells) {
+                        Some(c) => c,
+                        None => return rating
+                    };
+                    place(&mut cells, c, solution.values[c].value);
+                    rating.add(GUESS_WEIGHT, None);
+                    rating.guesses += 1;
+                }
+            }
+        }
+    }
+}
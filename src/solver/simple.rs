@@ -1,5 +1,6 @@
 use super::*;
 use ::grid::Cell;
+use super::step::{Step, Technique};
 
 /// Remove possibilities based on adjacent values
 macro_rules! run {
@@ -14,11 +15,11 @@ macro_rules! run {
                     let cj = $cells[j];
                     if ci.value != -1 {
                         if cj.value == -1 && cj.is_possible(ci.value) {
-                            let mut cell = &mut $cells[j];
+                            let cell = &mut $cells[j];
                             changed |= cell.remove_possible(ci.value);
                         }
                     } else if cj.value != -1 && ci.is_possible(cj.value) {
-                        let mut cell = &mut $cells[i];
+                        let cell = &mut $cells[i];
                         changed |= cell.remove_possible(cj.value);
                     }
                 }
@@ -45,3 +46,16 @@ pub fn columns(cells: &mut [Cell; 81]) -> bool {
 pub fn boxes(cells: &mut [Cell; 81]) -> bool {
     run!(cells, box_loc)
 }
+
+/// Find a cell with only one possible value left
+pub fn find(cands: &[u16; 81]) -> Option<Step> {
+    for i in 0..81 {
+        if cands[i].count_ones() == 1 {
+            let mut step = Step::new(Technique::NakedSingle);
+            step.cells.push(i);
+            step.placed.push((i, cands[i].trailing_zeros() as i8));
+            return Some(step);
+        }
+    }
+    None
+}
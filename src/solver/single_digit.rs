@@ -0,0 +1,146 @@
+use super::step::{Step, Technique, Unit, PEERS, sees, units_of};
+
+/// Find two rows (or columns) in which a value is only possible in two
+/// cells, with one end of each in the same column (or row). The value can be
+/// removed from cells seeing both other ends.
+pub fn skyscraper(cands: &[u16; 81]) -> Option<Step> {
+    for v in 0..9 {
+        let bit = 1 << v;
+        for &rows in [true, false].iter() {
+            // the line crossing the links
+            let cross = |i: usize| if rows { i % 9 } else { i / 9 };
+            let links = strong_links(cands, bit, rows);
+            for (n, &(u1, a1, b1)) in links.iter().enumerate() {
+                for &(u2, a2, b2) in links[n + 1..].iter() {
+                    // try each end of both links as the base
+                    for &(base1, roof1) in [(a1, b1), (b1, a1)].iter() {
+                        for &(base2, roof2) in [(a2, b2), (b2, a2)].iter() {
+                            if cross(base1) != cross(base2) || cross(roof1) == cross(roof2) {
+                                continue;
+                            }
+                            let mut step = Step::new(Technique::Skyscraper);
+                            push_seen_by_both(&mut step, cands, v, roof1, roof2);
+                            if !step.eliminated.is_empty() {
+                                step.units = vec![u1, u2];
+                                step.cells = vec![roof1, base1, base2, roof2];
+                                return Some(step);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Find a row and a column in which a value is only possible in two cells,
+/// with one end of each in the same box. The value can be removed from
+/// cells seeing both other ends.
+pub fn two_string_kite(cands: &[u16; 81]) -> Option<Step> {
+    for v in 0..9 {
+        let bit = 1 << v;
+        let cols = strong_links(cands, bit, false);
+        for &(row, a1, b1) in strong_links(cands, bit, true).iter() {
+            for &(col, a2, b2) in cols.iter() {
+                for &(box1, tail1) in [(a1, b1), (b1, a1)].iter() {
+                    for &(box2, tail2) in [(a2, b2), (b2, a2)].iter() {
+                        let bx = units_of(box1).2;
+                        if box1 == box2 || tail1 == tail2 || bx != units_of(box2).2 {
+                            continue;
+                        }
+                        let mut step = Step::new(Technique::TwoStringKite);
+                        push_seen_by_both(&mut step, cands, v, tail1, tail2);
+                        if !step.eliminated.is_empty() {
+                            step.units = vec![row, col, Unit::Box(bx)];
+                            step.cells = vec![tail1, box1, box2, tail2];
+                            return Some(step);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Find a box in which a value is confined to one row and one column, and a
+/// column (or row) in which the value is only possible in two cells, one of
+/// them in that row (or column). The value can be removed where the other
+/// end lines up with the box's column (or row).
+pub fn empty_rectangle(cands: &[u16; 81]) -> Option<Step> {
+    for v in 0..9 {
+        let bit = 1 << v;
+        for bx in 0..9 {
+            let in_box: Vec<usize> = Unit::Box(bx).cells().iter().cloned()
+                .filter(|&i| cands[i] & bit != 0)
+                .collect();
+            if in_box.len() < 2 {
+                continue;
+            }
+            let (band, stack) = (bx / 3, bx % 3);
+            for row in band * 3..band * 3 + 3 {
+                for col in stack * 3..stack * 3 + 3 {
+                    if !in_box.iter().all(|&i| i / 9 == row || i % 9 == col) {
+                        continue;
+                    }
+                    for &rows in [true, false].iter() {
+                        for &(unit, a, b) in strong_links(cands, bit, rows).iter() {
+                            for &(near, far) in [(a, b), (b, a)].iter() {
+                                // near end lines up with the cross outside the
+                                // box, the far end is clear of the box
+                                let target = if rows {
+                                    if near % 9 != col || near / 27 == band
+                                        || far % 9 / 3 == stack {
+                                        continue;
+                                    }
+                                    row * 9 + far % 9
+                                } else {
+                                    if near / 9 != row || near % 9 / 3 == stack
+                                        || far / 27 == band {
+                                        continue;
+                                    }
+                                    far / 9 * 9 + col
+                                };
+                                if cands[target] & bit != 0 {
+                                    let mut step = Step::new(Technique::EmptyRectangle);
+                                    step.eliminated.push((target, v));
+                                    step.units = vec![Unit::Box(bx), unit];
+                                    step.cells = in_box.clone();
+                                    step.cells.push(near);
+                                    step.cells.push(far);
+                                    return Some(step);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Rows (or columns) in which the value is only possible in two cells
+fn strong_links(cands: &[u16; 81], bit: u16, rows: bool) -> Vec<(Unit, usize, usize)> {
+    let mut links = Vec::new();
+    for major in 0..9 {
+        let unit = if rows { Unit::Row(major) } else { Unit::Column(major) };
+        let cells: Vec<usize> = unit.cells().iter().cloned()
+            .filter(|&i| cands[i] & bit != 0)
+            .collect();
+        if cells.len() == 2 {
+            links.push((unit, cells[0], cells[1]));
+        }
+    }
+    links
+}
+
+/// Adds `v` as eliminated from every cell seeing both `a` and `b`
+fn push_seen_by_both(step: &mut Step, cands: &[u16; 81], v: i8, a: usize, b: usize) {
+    for &i in PEERS[a].iter() {
+        if i != b && cands[i] & (1 << v) != 0 && sees(i, b) {
+            step.eliminated.push((i, v));
+        }
+    }
+}
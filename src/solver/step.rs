@@ -0,0 +1,650 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem;
+
+use super::*;
+use ::grid::Cell;
+
+/// A named solving technique
+#[derive(Copy, Clone, Debug)]
+pub enum Technique {
+    /// Only one value remains possible in a cell
+    NakedSingle,
+    /// A value is possible in only one cell of a row, column or box
+    HiddenSingle,
+    /// A value in a box is confined to one row or column
+    Pointing,
+    /// A value in a row or column is confined to one box
+    Claiming,
+    /// Two cells in a unit share the same two possible values
+    NakedPair,
+    /// A value in two rows is confined to the same two columns, or vice versa
+    XWing,
+    /// A value in three rows is confined to the same three columns, or vice
+    /// versa
+    Swordfish,
+    /// A value in four rows is confined to the same four columns, or vice
+    /// versa
+    Jellyfish,
+    /// An X-Wing with extra candidates confined to one box
+    FinnedXWing,
+    /// A finned X-Wing missing a candidate from one of its corners
+    SashimiXWing,
+    /// A swordfish with extra candidates confined to one box
+    FinnedSwordfish,
+    /// A finned swordfish missing candidates from its pattern
+    SashimiSwordfish,
+    /// Three cells in a unit share the same three possible values
+    NakedTriple,
+    /// Four cells in a unit share the same four possible values
+    NakedQuad,
+    /// Two values in a unit are only possible in the same two cells
+    HiddenPair,
+    /// Three values in a unit are only possible in the same three cells
+    HiddenTriple,
+    /// Four values in a unit are only possible in the same four cells
+    HiddenQuad,
+    /// A cell with values XY sees cells with values XZ and YZ
+    XYWing,
+    /// A cell with values XYZ sees cells with values XZ and YZ
+    XYZWing,
+    /// Two cells with values XY joined by a unit in which X is only possible
+    /// in two cells
+    WWing,
+    /// Two parallel lines with a value in two cells each, lined up at one end
+    Skyscraper,
+    /// A row and a column with a value in two cells each, meeting in a box
+    TwoStringKite,
+    /// A value in a box confined to a cross, linked to a line outside it
+    EmptyRectangle,
+    /// A chain of conjugate pairs of one value, colored alternately
+    SimpleColoring,
+    /// Two chains of conjugate pairs of one value which see each other
+    MultiColoring,
+    /// Candidates of all values joined by strong links, colored alternately
+    Medusa,
+    /// A loop of one value alternating strong and weak links
+    XCycle,
+    /// A chain of cells with two possible values, each sharing one with the
+    /// next
+    XYChain,
+    /// A chain of candidates alternating strong and weak links
+    Aic,
+    /// An alternating inference chain through groups of candidates in a
+    /// box-line intersection
+    GroupedAic,
+    /// A rectangle of cells sharing two values, one corner with extra values
+    UniqueRectangle1,
+    /// A rectangle with one extra value in two corners on one side
+    UniqueRectangle2,
+    /// A rectangle with extra values on one side forming a naked subset
+    UniqueRectangle3,
+    /// A rectangle with extra values on one side, where one of the pair is
+    /// confined to that side in a unit
+    UniqueRectangle4,
+    /// A rectangle with one extra value in diagonal corners or three corners
+    UniqueRectangle5,
+    /// A rectangle with extra values in diagonal corners, where one of the
+    /// pair is confined to the rectangle in its rows or columns
+    UniqueRectangle6,
+    /// A rectangle where one of the pair is confined to the rectangle in the
+    /// row and column of a corner
+    HiddenUniqueRectangle,
+    /// Every unknown cell has two possible values except one with three
+    BugPlusOne,
+    /// Two almost locked sets joined by a restricted common value
+    AlsXz,
+    /// Two almost locked sets each joined to a third by a different
+    /// restricted common value
+    AlsXyWing,
+    /// A cell with an almost locked set for each of its values
+    DeathBlossom,
+    /// Cells of a box-line intersection locked together with cells of the
+    /// box and the line
+    SueDeCoq,
+    /// A value which leads to a contradiction when assumed
+    Nishio,
+    /// Every value of a cell leads to the same conclusion
+    CellForcingChain,
+    /// Every place for a value in a unit leads to the same conclusion
+    UnitForcingChain,
+    /// A technique of a user-defined `Strategy`, with its name and weight
+    Custom(&'static str, f32)
+}
+
+/// Every technique, in the order the solver tries them
+///
+/// Techniques reported by the finder of another follow it, e.g. sashimi
+/// X-Wings come right after finned X-Wings.
+pub static TECHNIQUES: [Technique; 45] = [
+    Technique::NakedSingle,
+    Technique::HiddenSingle,
+    Technique::Pointing,
+    Technique::Claiming,
+    Technique::NakedPair,
+    Technique::XWing,
+    Technique::HiddenPair,
+    Technique::NakedTriple,
+    Technique::Skyscraper,
+    Technique::TwoStringKite,
+    Technique::EmptyRectangle,
+    Technique::FinnedXWing,
+    Technique::SashimiXWing,
+    Technique::Swordfish,
+    Technique::FinnedSwordfish,
+    Technique::SashimiSwordfish,
+    Technique::HiddenTriple,
+    Technique::XYWing,
+    Technique::SimpleColoring,
+    Technique::XYZWing,
+    Technique::WWing,
+    Technique::UniqueRectangle1,
+    Technique::UniqueRectangle4,
+    Technique::UniqueRectangle2,
+    Technique::UniqueRectangle5,
+    Technique::UniqueRectangle6,
+    Technique::UniqueRectangle3,
+    Technique::HiddenUniqueRectangle,
+    Technique::BugPlusOne,
+    Technique::MultiColoring,
+    Technique::Medusa,
+    Technique::NakedQuad,
+    Technique::Jellyfish,
+    Technique::HiddenQuad,
+    Technique::SueDeCoq,
+    Technique::XCycle,
+    Technique::XYChain,
+    Technique::Aic,
+    Technique::GroupedAic,
+    Technique::AlsXz,
+    Technique::AlsXyWing,
+    Technique::DeathBlossom,
+    Technique::Nishio,
+    Technique::CellForcingChain,
+    Technique::UnitForcingChain
+];
+
+impl Technique {
+    /// Human readable name of the technique
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Technique::NakedSingle => "Naked Single",
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::Pointing => "Pointing",
+            Technique::Claiming => "Claiming",
+            Technique::NakedPair => "Naked Pair",
+            Technique::XWing => "X-Wing",
+            Technique::Swordfish => "Swordfish",
+            Technique::Jellyfish => "Jellyfish",
+            Technique::FinnedXWing => "Finned X-Wing",
+            Technique::SashimiXWing => "Sashimi X-Wing",
+            Technique::FinnedSwordfish => "Finned Swordfish",
+            Technique::SashimiSwordfish => "Sashimi Swordfish",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::NakedQuad => "Naked Quad",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::HiddenQuad => "Hidden Quad",
+            Technique::XYWing => "XY-Wing",
+            Technique::XYZWing => "XYZ-Wing",
+            Technique::WWing => "W-Wing",
+            Technique::Skyscraper => "Skyscraper",
+            Technique::TwoStringKite => "2-String Kite",
+            Technique::EmptyRectangle => "Empty Rectangle",
+            Technique::SimpleColoring => "Simple Coloring",
+            Technique::MultiColoring => "Multi-Coloring",
+            Technique::Medusa => "3D Medusa",
+            Technique::XCycle => "X-Cycle",
+            Technique::XYChain => "XY-Chain",
+            Technique::Aic => "AIC",
+            Technique::GroupedAic => "Grouped AIC",
+            Technique::UniqueRectangle1 => "Unique Rectangle Type 1",
+            Technique::UniqueRectangle2 => "Unique Rectangle Type 2",
+            Technique::UniqueRectangle3 => "Unique Rectangle Type 3",
+            Technique::UniqueRectangle4 => "Unique Rectangle Type 4",
+            Technique::UniqueRectangle5 => "Unique Rectangle Type 5",
+            Technique::UniqueRectangle6 => "Unique Rectangle Type 6",
+            Technique::HiddenUniqueRectangle => "Hidden Unique Rectangle",
+            Technique::BugPlusOne => "BUG+1",
+            Technique::AlsXz => "ALS-XZ",
+            Technique::AlsXyWing => "ALS-XY-Wing",
+            Technique::DeathBlossom => "Death Blossom",
+            Technique::SueDeCoq => "Sue de Coq",
+            Technique::Nishio => "Nishio Forcing Chain",
+            Technique::CellForcingChain => "Cell Forcing Chain",
+            Technique::UnitForcingChain => "Unit Forcing Chain",
+            Technique::Custom(name, _) => name
+        }
+    }
+
+    /// Difficulty level the technique belongs to
+    pub fn difficulty(&self) -> Difficulty {
+        match *self {
+            Technique::NakedSingle => Difficulty::Easy,
+            Technique::HiddenSingle => Difficulty::Medium,
+            Technique::Pointing | Technique::Claiming => Difficulty::Hard,
+            Technique::NakedPair
+                | Technique::XWing
+                | Technique::NakedTriple
+                | Technique::HiddenPair => Difficulty::Expert,
+            Technique::Swordfish
+                | Technique::Jellyfish
+                | Technique::FinnedXWing
+                | Technique::SashimiXWing
+                | Technique::FinnedSwordfish
+                | Technique::SashimiSwordfish
+                | Technique::NakedQuad
+                | Technique::HiddenTriple
+                | Technique::HiddenQuad
+                | Technique::XYWing
+                | Technique::XYZWing
+                | Technique::WWing
+                | Technique::Skyscraper
+                | Technique::TwoStringKite
+                | Technique::EmptyRectangle
+                | Technique::SimpleColoring
+                | Technique::MultiColoring
+                | Technique::Medusa
+                | Technique::XCycle
+                | Technique::XYChain
+                | Technique::Aic
+                | Technique::GroupedAic
+                | Technique::UniqueRectangle1
+                | Technique::UniqueRectangle2
+                | Technique::UniqueRectangle3
+                | Technique::UniqueRectangle4
+                | Technique::UniqueRectangle5
+                | Technique::UniqueRectangle6
+                | Technique::HiddenUniqueRectangle
+                | Technique::BugPlusOne
+                | Technique::AlsXz
+                | Technique::AlsXyWing
+                | Technique::DeathBlossom
+                | Technique::SueDeCoq
+                | Technique::Nishio
+                | Technique::CellForcingChain
+                | Technique::UnitForcingChain
+                | Technique::Custom(..) => Difficulty::Diabolical
+        }
+    }
+
+    /// Whether the technique relies on the puzzle having a unique solution
+    pub fn assumes_unique(&self) -> bool {
+        matches!(*self, Technique::UniqueRectangle1
+                 | Technique::UniqueRectangle2
+                 | Technique::UniqueRectangle3
+                 | Technique::UniqueRectangle4
+                 | Technique::UniqueRectangle5
+                 | Technique::UniqueRectangle6
+                 | Technique::HiddenUniqueRectangle
+                 | Technique::BugPlusOne)
+    }
+
+    /// Weight of a step made by the technique, on the same scale as
+    /// Sudoku Explainer ratings
+    pub fn weight(&self) -> f32 {
+        match *self {
+            Technique::NakedSingle => 2.3,
+            Technique::HiddenSingle => 1.5,
+            Technique::Pointing => 2.6,
+            Technique::Claiming => 2.8,
+            Technique::NakedPair => 3.0,
+            Technique::XWing => 3.2,
+            Technique::Swordfish => 3.8,
+            Technique::Jellyfish => 5.2,
+            Technique::FinnedXWing => 3.4,
+            Technique::SashimiXWing => 3.5,
+            Technique::FinnedSwordfish => 4.0,
+            Technique::SashimiSwordfish => 4.1,
+            Technique::NakedTriple => 3.6,
+            Technique::NakedQuad => 5.0,
+            Technique::HiddenPair => 3.4,
+            Technique::HiddenTriple => 4.0,
+            Technique::HiddenQuad => 5.4,
+            Technique::XYWing => 4.2,
+            Technique::XYZWing => 4.4,
+            Technique::WWing => 4.4,
+            Technique::Skyscraper => 3.3,
+            Technique::TwoStringKite => 3.4,
+            Technique::EmptyRectangle => 3.4,
+            Technique::SimpleColoring => 4.3,
+            Technique::MultiColoring => 4.6,
+            Technique::Medusa => 4.8,
+            Technique::XCycle => 6.5,
+            Technique::XYChain => 6.6,
+            Technique::Aic => 7.0,
+            Technique::GroupedAic => 7.2,
+            Technique::UniqueRectangle1 => 4.5,
+            Technique::UniqueRectangle2 => 4.6,
+            Technique::UniqueRectangle3 => 4.7,
+            Technique::UniqueRectangle4 => 4.5,
+            Technique::UniqueRectangle5 => 4.6,
+            Technique::UniqueRectangle6 => 4.6,
+            Technique::HiddenUniqueRectangle => 4.7,
+            Technique::BugPlusOne => 5.6,
+            Technique::AlsXz => 7.5,
+            Technique::AlsXyWing => 8.0,
+            Technique::DeathBlossom => 8.5,
+            Technique::SueDeCoq => 5.6,
+            Technique::Nishio => 8.8,
+            Technique::CellForcingChain => 9.0,
+            Technique::UnitForcingChain => 9.2,
+            Technique::Custom(_, weight) => weight
+        }
+    }
+}
+
+// f32 is neither Eq nor Hash, custom techniques compare the bits of their
+// weight instead
+impl PartialEq for Technique {
+    fn eq(&self, other: &Technique) -> bool {
+        match (*self, *other) {
+            (Technique::Custom(a, x), Technique::Custom(b, y)) => {
+                a == b && x.to_bits() == y.to_bits()
+            },
+            (a, b) => mem::discriminant(&a) == mem::discriminant(&b)
+        }
+    }
+}
+
+impl Eq for Technique {}
+
+impl Hash for Technique {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        if let Technique::Custom(name, weight) = *self {
+            name.hash(state);
+            weight.to_bits().hash(state);
+        }
+    }
+}
+
+impl fmt::Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A row, column or box, numbered 0-8
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Unit {
+    Row(usize),
+    Column(usize),
+    Box(usize)
+}
+
+impl Unit {
+    /// All 27 units, boxes first
+    pub fn all() -> [Unit; 27] {
+        let mut units = [Unit::Row(0); 27];
+        for i in 0..9 {
+            units[i] = Unit::Box(i);
+            units[i + 9] = Unit::Row(i);
+            units[i + 18] = Unit::Column(i);
+        }
+        units
+    }
+
+    /// Index of the `minor`th cell in this unit
+    #[inline]
+    pub fn cell(&self, minor: usize) -> usize {
+        match *self {
+            Unit::Row(major) => row_loc(major, minor),
+            Unit::Column(major) => col_loc(major, minor),
+            Unit::Box(major) => box_loc(major, minor)
+        }
+    }
+
+    /// Indices of all cells in this unit
+    pub fn cells(&self) -> [usize; 9] {
+        let mut cells = [0; 9];
+        for (minor, c) in cells.iter_mut().enumerate() {
+            *c = self.cell(minor);
+        }
+        cells
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Unit::Row(i) => write!(f, "row {}", i + 1),
+            Unit::Column(i) => write!(f, "column {}", i + 1),
+            Unit::Box(i) => write!(f, "box {}", i + 1)
+        }
+    }
+}
+
+/// A single deduction made by a technique
+///
+/// Cells are indices into the grid in row-major order, values are 0-8 as in
+/// `Cell::value`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub technique: Technique,
+    /// Units the deduction is based on
+    pub units: Vec<Unit>,
+    /// Cells forming the pattern
+    pub cells: Vec<usize>,
+    /// Values placed as (cell, value) pairs
+    pub placed: Vec<(usize, i8)>,
+    /// Possible values removed as (cell, value) pairs
+    pub eliminated: Vec<(usize, i8)>,
+    /// Groups of (cell, value) pairs which are either all true or all false,
+    /// for coloring techniques
+    pub colors: Vec<Vec<(usize, i8)>>,
+    /// Nodes of the chain in order, for chain techniques
+    pub chain: Vec<ChainNode>
+}
+
+/// Type of link between two nodes of a chain
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Link {
+    /// If one node is false the other is true
+    Strong,
+    /// If one node is true the other is false
+    Weak
+}
+
+/// A candidate in a chain, or a group of candidates for the same value
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ChainNode {
+    /// Cells of the node, more than one for a grouped node
+    pub cells: Vec<usize>,
+    pub value: i8,
+    /// Link to the next node, for the last node of a loop this links back
+    /// to the first, `None` at the end of other chains
+    pub link: Option<Link>
+}
+
+impl ChainNode {
+    /// A node for a single candidate
+    pub fn new(cell: usize, value: i8, link: Option<Link>) -> ChainNode {
+        ChainNode {
+            cells: vec![cell],
+            value,
+            link
+        }
+    }
+}
+
+impl fmt::Display for ChainNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value + 1)?;
+        if self.cells.len() == 1 {
+            f.write_str(&cell_name(self.cells[0]))?;
+        } else {
+            f.write_str("{")?;
+            for (n, &i) in self.cells.iter().enumerate() {
+                write!(f, "{}{}", if n == 0 { "" } else { " " }, cell_name(i))?;
+            }
+            f.write_str("}")?;
+        }
+        match self.link {
+            Some(Link::Strong) => f.write_str(" = "),
+            Some(Link::Weak) => f.write_str(" - "),
+            None => Ok(())
+        }
+    }
+}
+
+impl Step {
+    /// An empty step for the given technique
+    pub fn new(technique: Technique) -> Step {
+        Step {
+            technique,
+            units: Vec::new(),
+            cells: Vec::new(),
+            placed: Vec::new(),
+            eliminated: Vec::new(),
+            colors: Vec::new(),
+            chain: Vec::new()
+        }
+    }
+
+    /// Applies the deduction to the cells
+    ///
+    /// Placing a value also removes it from the possible values of the
+    /// cell's row, column and box. Cells left with a single possible value
+    /// are not set, that is a step of its own.
+    pub fn apply(&self, cells: &mut [Cell; 81]) {
+        for &(i, v) in self.eliminated.iter() {
+            cells[i].possible &= !(1 << v);
+        }
+        for &(i, v) in self.placed.iter() {
+            place(cells, i, v);
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.technique)?;
+        for (n, unit) in self.units.iter().enumerate() {
+            write!(f, "{} {}", if n == 0 { " in" } else { "," }, unit)?;
+        }
+        f.write_str(":")?;
+        for &(i, v) in self.placed.iter() {
+            write!(f, " {} = {}", cell_name(i), v + 1)?;
+        }
+        for &(i, v) in self.eliminated.iter() {
+            write!(f, " {} <> {}", cell_name(i), v + 1)?;
+        }
+        for (n, color) in self.colors.iter().enumerate() {
+            f.write_str(if n == 0 { ", colors [" } else { " | " })?;
+            for (m, &(i, v)) in color.iter().enumerate() {
+                write!(f, "{}{}{}", if m == 0 { "" } else { " " }, v + 1, cell_name(i))?;
+            }
+            if n == self.colors.len() - 1 {
+                f.write_str("]")?;
+            }
+        }
+        for (n, node) in self.chain.iter().enumerate() {
+            if n == 0 {
+                f.write_str(", chain ")?;
+            }
+            write!(f, "{}", node)?;
+        }
+        if let Some(&ChainNode { link: Some(_), .. }) = self.chain.last() {
+            // a loop ends where it started
+            write!(f, "{}", ChainNode { link: None, ..self.chain[0].clone() })?;
+        }
+        Ok(())
+    }
+}
+
+/// Adds each value in the bit field `values` as eliminated from cell `i`
+pub fn push_eliminated(step: &mut Step, i: usize, values: u16) {
+    for v in 0..9 {
+        if values & (1 << v) != 0 {
+            step.eliminated.push((i, v));
+        }
+    }
+}
+
+/// Sets the value of a cell and removes it from the possible values of the
+/// cell's row, column and box
+pub fn place(cells: &mut [Cell; 81], i: usize, value: i8) {
+    cells[i].value = value;
+    cells[i].possible = 1 << value;
+    for &p in PEERS[i].iter() {
+        if cells[p].value == -1 {
+            cells[p].possible &= !(1 << value);
+        }
+    }
+}
+
+/// Name of a cell in r1c1 notation
+pub fn cell_name(i: usize) -> String {
+    format!("r{}c{}", i / 9 + 1, i % 9 + 1)
+}
+
+/// Possible values of each unknown cell as a bit field, excluding values
+/// already present in its row, column or box
+///
+/// Known cells have no possible values
+pub fn candidates(cells: &[Cell; 81]) -> [u16; 81] {
+    let mut cands = [0; 81];
+    for i in 0..81 {
+        if cells[i].value == -1 {
+            let mut poss = cells[i].possible;
+            for &p in PEERS[i].iter() {
+                if cells[p].value != -1 {
+                    poss &= !(1 << cells[p].value);
+                }
+            }
+            cands[i] = poss;
+        }
+    }
+    cands
+}
+
+/// Removes values already present in each unknown cell's row, column or
+/// box from its possible values
+pub fn prune(cells: &mut [Cell; 81]) {
+    let cands = candidates(cells);
+    for (cell, &poss) in cells.iter_mut().zip(cands.iter()) {
+        if cell.value == -1 {
+            cell.possible = poss;
+        }
+    }
+}
+
+/// Row, column and box index of a cell
+#[inline]
+pub fn units_of(i: usize) -> (usize, usize, usize) {
+    let (row, col) = (i / 9, i % 9);
+    (row, col, (row / 3) * 3 + col / 3)
+}
+
+/// Whether two distinct cells share a row, column or box
+#[inline]
+pub fn sees(a: usize, b: usize) -> bool {
+    let (ra, ca, ba) = units_of(a);
+    let (rb, cb, bb) = units_of(b);
+    a != b && (ra == rb || ca == cb || ba == bb)
+}
+
+/// Indices of the 20 cells sharing a row, column or box with each cell
+pub static PEERS: [[usize; 20]; 81] = peers();
+
+const fn peers() -> [[usize; 20]; 81] {
+    let mut all = [[0; 20]; 81];
+    let mut i = 0;
+    while i < 81 {
+        let (row, col) = (i / 9, i % 9);
+        let mut n = 0;
+        let mut j = 0;
+        while j < 81 {
+            let (r, c) = (j / 9, j % 9);
+            if j != i && (r == row || c == col
+                || (r / 3 == row / 3 && c / 3 == col / 3)) {
+                all[i][n] = j;
+                n += 1;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    all
+}
@@ -0,0 +1,246 @@
+use super::*;
+use ::grid::Cell;
+
+/// Outcome of applying a strategy to the cells
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StepResult {
+    /// Values were placed or possible values removed
+    Changed,
+    /// The strategy found nothing to do
+    Unchanged
+}
+
+/// A solving technique the `Solver` can be built from
+pub trait Strategy {
+    /// Human readable name of the strategy
+    fn name(&self) -> &'static str;
+
+    /// Weight of a deduction made by the strategy, on the same scale as
+    /// `Technique::weight`
+    fn weight(&self) -> f32;
+
+    /// Technique reported in the steps found by the strategy
+    ///
+    /// `Step::new(self.technique())` gives steps carrying the strategy's
+    /// name and weight.
+    fn technique(&self) -> Technique {
+        Technique::Custom(self.name(), self.weight())
+    }
+
+    /// Makes deductions in the cells
+    ///
+    /// Cells left with a single possible value should be set with
+    /// `Cell::check_possible`.
+    fn apply(&self, cells: &mut [Cell; 81]) -> StepResult;
+
+    /// Finds a single deduction in the possible values of each cell, as
+    /// returned by `step::candidates`, to explain it
+    ///
+    /// Strategies which only work on the cells directly return `None`, they
+    /// are skipped when solving one step at a time.
+    fn find(&self, _cands: &[u16; 81]) -> Option<Step> {
+        None
+    }
+
+    /// Whether the strategy is quick enough to apply right after another
+    /// one made progress, rather than only once the easier ones are stuck
+    fn is_basic(&self) -> bool {
+        false
+    }
+
+    /// Whether the strategy relies on the puzzle having a unique solution,
+    /// see `Solver::assume_unique`
+    fn assumes_unique(&self) -> bool {
+        false
+    }
+
+    /// Whether `apply` removes every placed value from the possible values
+    /// of its row, column and box, if none of a solver's strategies do the
+    /// solver does so itself before each pass
+    fn prunes_peers(&self) -> bool {
+        false
+    }
+}
+
+/// Finds the first deduction made by a technique
+type Finder = fn(&[u16; 81]) -> Option<Step>;
+
+/// Applies a technique to the cells directly, returns `true` if anything
+/// changed
+type Bulk = fn(&mut [Cell; 81]) -> bool;
+
+/// One of the techniques provided by this crate
+#[derive(Copy, Clone)]
+pub struct Builtin {
+    technique: Technique,
+    find: Finder,
+    /// Quicker version of `find` working on the cells, if there is one
+    bulk: Option<Bulk>
+}
+
+impl Strategy for Builtin {
+    fn name(&self) -> &'static str {
+        self.technique.name()
+    }
+
+    fn weight(&self) -> f32 {
+        self.technique.weight()
+    }
+
+    /// The technique, for finders reporting several this is the simplest
+    fn technique(&self) -> Technique {
+        self.technique
+    }
+
+    fn apply(&self, cells: &mut [Cell; 81]) -> StepResult {
+        if let Some(bulk) = self.bulk {
+            return if bulk(cells) { StepResult::Changed } else { StepResult::Unchanged };
+        }
+        match (self.find)(&step::candidates(cells)) {
+            Some(step) => {
+                step.apply(cells);
+                for &(i, _) in step.eliminated.iter() {
+                    cells[i].check_possible();
+                }
+                StepResult::Changed
+            },
+            None => StepResult::Unchanged
+        }
+    }
+
+    fn find(&self, cands: &[u16; 81]) -> Option<Step> {
+        (self.find)(cands)
+    }
+
+    fn is_basic(&self) -> bool {
+        self.bulk.is_some()
+    }
+
+    fn assumes_unique(&self) -> bool {
+        self.technique.assumes_unique()
+    }
+
+    fn prunes_peers(&self) -> bool {
+        self.technique == Technique::NakedSingle
+    }
+}
+
+/// Every built-in technique from easiest to hardest, the first `BASIC` can
+/// be applied to the cells directly
+static BUILTIN: [Builtin; 41] = [
+    Builtin { technique: Technique::NakedSingle, find: simple::find, bulk: Some(simple_units) },
+    Builtin { technique: Technique::HiddenSingle, find: hidden::find, bulk: Some(hidden_units) },
+    Builtin { technique: Technique::Pointing, find: locked::pointing, bulk: Some(pointing_units) },
+    Builtin { technique: Technique::Claiming, find: locked::claiming, bulk: Some(claiming_units) },
+    Builtin { technique: Technique::NakedPair, find: naked::find, bulk: Some(naked_units) },
+    Builtin { technique: Technique::XWing, find: fish::x_wing, bulk: None },
+    Builtin { technique: Technique::HiddenPair, find: hidden::find_pair, bulk: None },
+    Builtin { technique: Technique::NakedTriple, find: naked::find_triple, bulk: None },
+    Builtin { technique: Technique::Skyscraper, find: single_digit::skyscraper, bulk: None },
+    Builtin { technique: Technique::TwoStringKite, find: single_digit::two_string_kite, bulk: None },
+    Builtin { technique: Technique::EmptyRectangle, find: single_digit::empty_rectangle, bulk: None },
+    Builtin { technique: Technique::FinnedXWing, find: fish::finned_x_wing, bulk: None },
+    Builtin { technique: Technique::Swordfish, find: fish::swordfish, bulk: None },
+    Builtin { technique: Technique::FinnedSwordfish, find: fish::finned_swordfish, bulk: None },
+    Builtin { technique: Technique::HiddenTriple, find: hidden::find_triple, bulk: None },
+    Builtin { technique: Technique::XYWing, find: wings::xy_wing, bulk: None },
+    Builtin { technique: Technique::SimpleColoring, find: coloring::simple, bulk: None },
+    Builtin { technique: Technique::XYZWing, find: wings::xyz_wing, bulk: None },
+    Builtin { technique: Technique::WWing, find: wings::w_wing, bulk: None },
+    Builtin { technique: Technique::UniqueRectangle1, find: unique::type_1, bulk: None },
+    Builtin { technique: Technique::UniqueRectangle4, find: unique::type_4, bulk: None },
+    Builtin { technique: Technique::UniqueRectangle2, find: unique::type_2, bulk: None },
+    Builtin { technique: Technique::UniqueRectangle6, find: unique::type_6, bulk: None },
+    Builtin { technique: Technique::UniqueRectangle3, find: unique::type_3, bulk: None },
+    Builtin { technique: Technique::HiddenUniqueRectangle, find: unique::hidden, bulk: None },
+    Builtin { technique: Technique::BugPlusOne, find: unique::bug, bulk: None },
+    Builtin { technique: Technique::MultiColoring, find: coloring::multi, bulk: None },
+    Builtin { technique: Technique::Medusa, find: medusa::find, bulk: None },
+    Builtin { technique: Technique::NakedQuad, find: naked::find_quad, bulk: None },
+    Builtin { technique: Technique::Jellyfish, find: fish::jellyfish, bulk: None },
+    Builtin { technique: Technique::HiddenQuad, find: hidden::find_quad, bulk: None },
+    Builtin { technique: Technique::SueDeCoq, find: locked::sue_de_coq, bulk: None },
+    Builtin { technique: Technique::XCycle, find: chains::x_cycle, bulk: None },
+    Builtin { technique: Technique::XYChain, find: chains::xy_chain, bulk: None },
+    Builtin { technique: Technique::Aic, find: chains::aic, bulk: None },
+    Builtin { technique: Technique::AlsXz, find: als::xz, bulk: None },
+    Builtin { technique: Technique::AlsXyWing, find: als::xy_wing, bulk: None },
+    Builtin { technique: Technique::DeathBlossom, find: als::death_blossom, bulk: None },
+    Builtin { technique: Technique::Nishio, find: forcing::nishio, bulk: None },
+    Builtin { technique: Technique::CellForcingChain, find: forcing::cell, bulk: None },
+    Builtin { technique: Technique::UnitForcingChain, find: forcing::unit, bulk: None }
+];
+const BASIC: usize = 5;
+
+/// The built-in techniques up to the given `Difficulty`, from easiest to
+/// hardest
+pub fn builtin(max_difficulty: Difficulty) -> Vec<Box<dyn Strategy>> {
+    BUILTIN.iter()
+        .filter(|b| b.technique.difficulty() <= max_difficulty)
+        .map(|&b| Box::new(b) as Box<dyn Strategy>)
+        .collect()
+}
+
+/// The built-in techniques in the set, from easiest to hardest
+///
+/// Sashimi fish, type 5 unique rectangles and grouped AICs are found along
+/// with finned fish, type 2 unique rectangles and AICs, allowing either one
+/// allows both.
+pub fn techniques(allowed: &[Technique]) -> Vec<Box<dyn Strategy>> {
+    BUILTIN.iter()
+        .filter(|b| allowed.iter().any(|&t| found_by(t) == b.technique))
+        .map(|&b| Box::new(b) as Box<dyn Strategy>)
+        .collect()
+}
+
+/// The technique of the `Builtin` which finds the given technique
+fn found_by(technique: Technique) -> Technique {
+    match technique {
+        Technique::SashimiXWing => Technique::FinnedXWing,
+        Technique::SashimiSwordfish => Technique::FinnedSwordfish,
+        Technique::UniqueRectangle5 => Technique::UniqueRectangle2,
+        Technique::GroupedAic => Technique::Aic,
+        t => t
+    }
+}
+
+/// The built-in techniques which are applied to the cells directly: naked
+/// and hidden singles, pointing, claiming and naked pairs
+pub fn basic() -> Vec<Box<dyn Strategy>> {
+    BUILTIN[..BASIC].iter()
+        .map(|&b| Box::new(b) as Box<dyn Strategy>)
+        .collect()
+}
+
+fn simple_units(cells: &mut [Cell; 81]) -> bool {
+    let mut changed = simple::rows(cells);
+    changed |= simple::columns(cells);
+    changed |= simple::boxes(cells);
+    changed
+}
+
+fn hidden_units(cells: &mut [Cell; 81]) -> bool {
+    let mut changed = hidden::rows(cells);
+    changed |= hidden::columns(cells);
+    changed |= hidden::boxes(cells);
+    changed
+}
+
+fn pointing_units(cells: &mut [Cell; 81]) -> bool {
+    let mut changed = locked::rows(cells);
+    changed |= locked::columns(cells);
+    changed
+}
+
+fn claiming_units(cells: &mut [Cell; 81]) -> bool {
+    let mut changed = locked::box_rows(cells);
+    changed |= locked::box_cols(cells);
+    changed
+}
+
+fn naked_units(cells: &mut [Cell; 81]) -> bool {
+    let mut changed = naked::rows(cells);
+    changed |= naked::columns(cells);
+    changed |= naked::boxes(cells);
+    changed
+}
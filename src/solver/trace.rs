@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::step::{Step, Technique, cell_name};
+
+/// Something the solver did while solving a puzzle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// A logical deduction
+    Step(Step),
+    /// A value tried in a cell when no technique applied
+    Guess {
+        cell: usize,
+        value: i8,
+        /// Number of guesses this one is nested in, starting from 1
+        depth: usize,
+        /// Whether the guess led to a contradiction and was undone
+        backtracked: bool
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Event::Step(ref step) => write!(f, "{}", step),
+            Event::Guess { cell, value, depth, backtracked } => {
+                write!(f, "Guess (depth {}): {} = {}", depth, cell_name(cell), value + 1)?;
+                if backtracked {
+                    f.write_str(", backtracked")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Record of the path taken by `Solver::solve_traced`
+///
+/// Events made inside guesses which were later backtracked are kept, and
+/// are included in the counts
+#[derive(Clone, Debug, Default)]
+pub struct Trace {
+    /// Every deduction and guess in the order they were made
+    pub events: Vec<Event>,
+    /// Number of steps made by each technique, by name
+    pub counts: HashMap<&'static str, usize>,
+    /// Total number of guesses
+    pub guesses: usize,
+    /// Deepest nesting of guesses
+    pub max_depth: usize
+}
+
+impl Trace {
+    pub fn new() -> Trace {
+        Trace::default()
+    }
+
+    /// Number of steps made by a technique
+    pub fn count(&self, technique: Technique) -> usize {
+        self.counts.get(technique.name()).cloned().unwrap_or(0)
+    }
+
+    /// Returns `true` if the puzzle was solved without guessing
+    pub fn is_logical(&self) -> bool {
+        self.guesses == 0
+    }
+
+    pub fn push_step(&mut self, step: Step) {
+        *self.counts.entry(step.technique.name()).or_insert(0) += 1;
+        self.events.push(Event::Step(step));
+    }
+
+    /// Records a guess, returns its index in `events`
+    pub fn push_guess(&mut self, cell: usize, value: i8, depth: usize) -> usize {
+        self.guesses += 1;
+        if depth > self.max_depth {
+            self.max_depth = depth;
+        }
+        self.events.push(Event::Guess {
+            cell,
+            value,
+            depth,
+            backtracked: false
+        });
+        self.events.len() - 1
+    }
+
+    /// Marks the guess at index `i` in `events` as backtracked
+    pub fn backtrack(&mut self, i: usize) {
+        if let Event::Guess { ref mut backtracked, .. } = self.events[i] {
+            *backtracked = true;
+        }
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for event in self.events.iter() {
+            writeln!(f, "{}", event)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,293 @@
+use super::step::{Step, Technique, Unit, PEERS, sees, units_of, push_eliminated};
+
+/// Corners of a rectangle, ordered top left, top right, bottom left, bottom
+/// right
+type Corners = [usize; 4];
+
+/// Find a rectangle of cells in two boxes sharing two possible values, where
+/// one corner has other values; both values can be removed from it
+pub fn type_1(cands: &[u16; 81]) -> Option<Step> {
+    for (corners, ab) in rectangles(cands) {
+        let roof = roof(cands, &corners, ab);
+        if roof.len() == 1 {
+            let mut step = rectangle_step(Technique::UniqueRectangle1, &corners);
+            push_eliminated(&mut step, roof[0], ab);
+            return Some(step);
+        }
+    }
+    None
+}
+
+/// Find a rectangle where the corners with other values all have the same
+/// single extra value; it must be in one of them, so it can be removed from
+/// cells seeing all of them
+///
+/// Reported as type 2 if two corners on one side have the extra value, or
+/// as type 5 if they are diagonal or there are three of them.
+pub fn type_2(cands: &[u16; 81]) -> Option<Step> {
+    for (corners, ab) in rectangles(cands) {
+        let roof = roof(cands, &corners, ab);
+        if roof.len() < 2 {
+            continue;
+        }
+        let extra = cands[roof[0]] & !ab;
+        if extra.count_ones() != 1 || roof.iter().any(|&i| cands[i] & !ab != extra) {
+            continue;
+        }
+        let side = roof.len() == 2 && sees(roof[0], roof[1]);
+        let technique = if side { Technique::UniqueRectangle2 } else { Technique::UniqueRectangle5 };
+        let mut step = rectangle_step(technique, &corners);
+        for &i in PEERS[roof[0]].iter() {
+            if cands[i] & extra != 0 && !roof.contains(&i)
+                && roof[1..].iter().all(|&j| sees(i, j)) {
+                push_eliminated(&mut step, i, extra);
+            }
+        }
+        if !step.eliminated.is_empty() {
+            return Some(step);
+        }
+    }
+    None
+}
+
+/// Find a rectangle with two corners on one side having other values, which
+/// together with other cells of a unit they share form a naked subset; the
+/// subset's values can be removed from the rest of the unit
+pub fn type_3(cands: &[u16; 81]) -> Option<Step> {
+    for (corners, ab) in rectangles(cands) {
+        let roof = roof(cands, &corners, ab);
+        if roof.len() != 2 {
+            continue;
+        }
+        // the roof acts as a single cell with the extra values
+        let extra = (cands[roof[0]] | cands[roof[1]]) & !ab;
+        for unit in shared_units(roof[0], roof[1]) {
+            let others: Vec<usize> = unit.cells().iter().cloned()
+                .filter(|&i| cands[i] != 0 && !roof.contains(&i))
+                .collect();
+            for set in 1..(1u32 << others.len()) {
+                let size = set.count_ones();
+                if size > 3 {
+                    continue;
+                }
+                let mut values = extra;
+                for (n, &i) in others.iter().enumerate() {
+                    if set & (1 << n) != 0 {
+                        values |= cands[i];
+                    }
+                }
+                if values.count_ones() != size + 1 {
+                    continue;
+                }
+                let mut step = rectangle_step(Technique::UniqueRectangle3, &corners);
+                for (n, &i) in others.iter().enumerate() {
+                    if set & (1 << n) == 0 {
+                        push_eliminated(&mut step, i, cands[i] & values);
+                    } else {
+                        step.cells.push(i);
+                    }
+                }
+                if !step.eliminated.is_empty() {
+                    step.units.push(unit);
+                    return Some(step);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Find a rectangle with two corners on one side having other values, where
+/// one of the pair is only possible in those two corners of a unit; the
+/// other value of the pair can be removed from both
+pub fn type_4(cands: &[u16; 81]) -> Option<Step> {
+    for (corners, ab) in rectangles(cands) {
+        let roof = roof(cands, &corners, ab);
+        if roof.len() != 2 {
+            continue;
+        }
+        for unit in shared_units(roof[0], roof[1]) {
+            for v in 0..9 {
+                let bit = 1 << v;
+                if ab & bit == 0 || !only_in(cands, unit, bit, &roof) {
+                    continue;
+                }
+                let mut step = rectangle_step(Technique::UniqueRectangle4, &corners);
+                for &i in roof.iter() {
+                    push_eliminated(&mut step, i, ab & !bit);
+                }
+                step.units.push(unit);
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// Find a rectangle with two diagonal corners having other values, where
+/// one of the pair is only possible in the rectangle in both of its rows or
+/// both of its columns; that value can be removed from both corners
+pub fn type_6(cands: &[u16; 81]) -> Option<Step> {
+    for (corners, ab) in rectangles(cands) {
+        let roof = roof(cands, &corners, ab);
+        if roof.len() != 2 || sees(roof[0], roof[1]) {
+            continue;
+        }
+        let (r1, c1, _) = units_of(corners[0]);
+        let (r2, c2, _) = units_of(corners[3]);
+        for v in 0..9 {
+            let bit = 1 << v;
+            if ab & bit == 0 {
+                continue;
+            }
+            let rows = only_in(cands, Unit::Row(r1), bit, &corners)
+                && only_in(cands, Unit::Row(r2), bit, &corners);
+            let cols = only_in(cands, Unit::Column(c1), bit, &corners)
+                && only_in(cands, Unit::Column(c2), bit, &corners);
+            if !rows && !cols {
+                continue;
+            }
+            let mut step = rectangle_step(Technique::UniqueRectangle6, &corners);
+            for &i in roof.iter() {
+                push_eliminated(&mut step, i, bit);
+            }
+            if rows {
+                step.units.extend([Unit::Row(r1), Unit::Row(r2)].iter());
+            } else {
+                step.units.extend([Unit::Column(c1), Unit::Column(c2)].iter());
+            }
+            return Some(step);
+        }
+    }
+    None
+}
+
+/// Find a rectangle with a corner having only the two values, where one of
+/// them is only possible in the rectangle in the row and column of the
+/// opposite corner; the other value can be removed from the opposite corner
+pub fn hidden(cands: &[u16; 81]) -> Option<Step> {
+    for (corners, ab) in rectangles(cands) {
+        for k in 0..4 {
+            let (floor, opposite) = (corners[k], corners[3 - k]);
+            if cands[floor] != ab {
+                continue;
+            }
+            let (row, col, _) = units_of(opposite);
+            for v in 0..9 {
+                let bit = 1 << v;
+                if ab & bit == 0
+                    || !only_in(cands, Unit::Row(row), bit, &corners)
+                    || !only_in(cands, Unit::Column(col), bit, &corners) {
+                    continue;
+                }
+                let mut step = rectangle_step(Technique::HiddenUniqueRectangle, &corners);
+                push_eliminated(&mut step, opposite, ab & !bit);
+                if !step.eliminated.is_empty() {
+                    step.units = vec![Unit::Row(row), Unit::Column(col)];
+                    return Some(step);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Find a grid where every unknown cell has two possible values except one
+/// with three, and every value is possible in exactly two cells of each unit
+/// except for one value in that cell's units (bivalue universal grave plus
+/// one); placing any other value there would leave a deadly pattern
+pub fn bug(cands: &[u16; 81]) -> Option<Step> {
+    let mut extra = None;
+    for i in 0..81 {
+        match cands[i].count_ones() {
+            0 | 2 => {},
+            3 if extra.is_none() => extra = Some(i),
+            _ => return None
+        }
+    }
+    let i = extra?;
+    for v in 0..9 {
+        let bit = 1 << v;
+        if cands[i] & bit == 0 {
+            continue;
+        }
+        let mut rest = *cands;
+        rest[i] &= !bit;
+        let grave = Unit::all().iter().all(|unit| {
+            (0..9).all(|w| {
+                let count = unit.cells().iter().filter(|&&j| rest[j] & (1 << w) != 0).count();
+                count == 0 || count == 2
+            })
+        });
+        if grave {
+            let mut step = Step::new(Technique::BugPlusOne);
+            step.cells.push(i);
+            step.placed.push((i, v));
+            return Some(step);
+        }
+    }
+    None
+}
+
+/// Rectangles of unknown cells in two rows, two columns and two boxes, with
+/// each pair of values possible in all four corners
+fn rectangles(cands: &[u16; 81]) -> Vec<(Corners, u16)> {
+    let mut found = Vec::new();
+    for r1 in 0..9 {
+        for r2 in r1 + 1..9 {
+            for c1 in 0..9 {
+                for c2 in c1 + 1..9 {
+                    // exactly one of the rows or columns share boxes
+                    if (r1 / 3 == r2 / 3) == (c1 / 3 == c2 / 3) {
+                        continue;
+                    }
+                    let corners = [r1 * 9 + c1, r1 * 9 + c2, r2 * 9 + c1, r2 * 9 + c2];
+                    let common = corners.iter().fold(0x1ff, |m, &i| m & cands[i]);
+                    for a in 0..9 {
+                        for b in a + 1..9 {
+                            let ab = (1 << a) | (1 << b);
+                            if common & ab == ab {
+                                found.push((corners, ab));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+/// Corners with possible values other than the pair
+fn roof(cands: &[u16; 81], corners: &Corners, ab: u16) -> Vec<usize> {
+    corners.iter().cloned().filter(|&i| cands[i] != ab).collect()
+}
+
+/// Units containing both cells
+fn shared_units(a: usize, b: usize) -> Vec<Unit> {
+    let (ra, ca, ba) = units_of(a);
+    let (rb, cb, bb) = units_of(b);
+    let mut units = Vec::new();
+    if ra == rb {
+        units.push(Unit::Row(ra));
+    }
+    if ca == cb {
+        units.push(Unit::Column(ca));
+    }
+    if ba == bb {
+        units.push(Unit::Box(ba));
+    }
+    units
+}
+
+/// Whether the value is only possible in the unit within `cells`
+fn only_in(cands: &[u16; 81], unit: Unit, bit: u16, cells: &[usize]) -> bool {
+    unit.cells().iter().all(|i| cands[*i] & bit == 0 || cells.contains(i))
+}
+
+/// A step for the technique with the rectangle as its pattern
+fn rectangle_step(technique: Technique, corners: &Corners) -> Step {
+    let mut step = Step::new(technique);
+    step.cells = corners.to_vec();
+    step
+}
@@ -0,0 +1,125 @@
+use super::step::{Step, Technique, Unit, PEERS, sees};
+
+/// Find a cell with two possible values XY (the pivot) which sees a cell XZ
+/// and a cell YZ (the pincers), Z can be removed from cells seeing both
+/// pincers
+pub fn xy_wing(cands: &[u16; 81]) -> Option<Step> {
+    for pivot in 0..81 {
+        let xy = cands[pivot];
+        if xy.count_ones() != 2 {
+            continue;
+        }
+        for (n, &a) in PEERS[pivot].iter().enumerate() {
+            let xz = cands[a];
+            if xz.count_ones() != 2 || (xz & xy).count_ones() != 1 {
+                continue;
+            }
+            let z = xz & !xy;
+            for &b in PEERS[pivot][n + 1..].iter() {
+                // the other pincer has the other pivot value and z
+                if cands[b] != (xy & !xz) | z {
+                    continue;
+                }
+                let mut step = Step::new(Technique::XYWing);
+                push_common(&mut step, cands, z, &[a, b], &[pivot, a, b]);
+                if !step.eliminated.is_empty() {
+                    step.cells = vec![pivot, a, b];
+                    return Some(step);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Find a cell with three possible values XYZ (the pivot) which sees a cell
+/// XZ and a cell YZ, Z can be removed from cells seeing all three
+pub fn xyz_wing(cands: &[u16; 81]) -> Option<Step> {
+    for pivot in 0..81 {
+        let xyz = cands[pivot];
+        if xyz.count_ones() != 3 {
+            continue;
+        }
+        for (n, &a) in PEERS[pivot].iter().enumerate() {
+            let xz = cands[a];
+            if xz.count_ones() != 2 || xz & !xyz != 0 {
+                continue;
+            }
+            for &b in PEERS[pivot][n + 1..].iter() {
+                let yz = cands[b];
+                if yz.count_ones() != 2 || yz & !xyz != 0 || yz == xz {
+                    continue;
+                }
+                let z = xz & yz;
+                let mut step = Step::new(Technique::XYZWing);
+                push_common(&mut step, cands, z, &[pivot, a, b], &[pivot, a, b]);
+                if !step.eliminated.is_empty() {
+                    step.cells = vec![pivot, a, b];
+                    return Some(step);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Find two cells with the same two possible values XY which don't see each
+/// other, connected by a unit in which X is only possible in two cells, one
+/// seeing each of them; Y can be removed from cells seeing both
+pub fn w_wing(cands: &[u16; 81]) -> Option<Step> {
+    for a in 0..81 {
+        let xy = cands[a];
+        if xy.count_ones() != 2 {
+            continue;
+        }
+        for b in a + 1..81 {
+            if cands[b] != xy || sees(a, b) {
+                continue;
+            }
+            for x in 0..9 {
+                let bit = 1 << x;
+                if xy & bit == 0 {
+                    continue;
+                }
+                let y = xy & !bit;
+                for &unit in Unit::all().iter() {
+                    let link: Vec<usize> = unit.cells().iter().cloned()
+                        .filter(|&i| cands[i] & bit != 0)
+                        .collect();
+                    if link.len() != 2 || link.contains(&a) || link.contains(&b) {
+                        continue;
+                    }
+                    let (p, q) = if sees(link[0], a) && sees(link[1], b) {
+                        (link[0], link[1])
+                    } else if sees(link[1], a) && sees(link[0], b) {
+                        (link[1], link[0])
+                    } else {
+                        continue;
+                    };
+                    let mut step = Step::new(Technique::WWing);
+                    push_common(&mut step, cands, y, &[a, b], &[a, b]);
+                    if !step.eliminated.is_empty() {
+                        step.units.push(unit);
+                        step.cells = vec![a, p, q, b];
+                        return Some(step);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Adds the single value in `value` as eliminated from every cell which sees
+/// all of `seen`, except the `pattern` cells
+fn push_common(step: &mut Step, cands: &[u16; 81], value: u16,
+               seen: &[usize], pattern: &[usize]) {
+    let v = value.trailing_zeros() as i8;
+    for &i in PEERS[seen[0]].iter() {
+        if cands[i] & value != 0
+            && !pattern.contains(&i)
+            && seen[1..].iter().all(|&s| sees(i, s)) {
+            step.eliminated.push((i, v));
+        }
+    }
+}
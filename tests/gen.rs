@@ -0,0 +1,57 @@
+extern crate sudoku;
+
+use sudoku::gen::{Generator, Difficulty};
+use sudoku::grid::Grid;
+use sudoku::solver::Solver;
+
+fn values(grid: &Grid) -> [i8; 81] {
+    let mut values = [-1; 81];
+    for (v, cell) in values.iter_mut().zip(grid.values.iter()) {
+        *v = cell.value;
+    }
+    values
+}
+
+fn solves_at(grid: &Grid, difficulty: Difficulty) -> bool {
+    Solver::new(difficulty).solve_values(&values(grid)).is_solved()
+}
+
+/// Generates a puzzle for the level and checks that it can be solved at
+/// that level but not the one below
+fn check_level(level: Difficulty) {
+    let grid = Generator::random().generate_with(level);
+    if level == Difficulty::Diabolical {
+        assert!(grid.solve().is_some(), "puzzle has no solution");
+    } else {
+        assert!(solves_at(&grid, level), "puzzle can't be solved at its level");
+    }
+    let levels = Difficulty::all();
+    if let Some(n) = levels.iter().position(|&l| l == level).filter(|&n| n > 0) {
+        assert!(!solves_at(&grid, levels[n - 1]), "puzzle is easier than its level");
+    }
+}
+
+#[test]
+fn generate_easy() {
+    check_level(Difficulty::Easy);
+}
+
+#[test]
+fn generate_medium() {
+    check_level(Difficulty::Medium);
+}
+
+#[test]
+fn generate_hard() {
+    check_level(Difficulty::Hard);
+}
+
+#[test]
+fn generate_expert() {
+    check_level(Difficulty::Expert);
+}
+
+#[test]
+fn generate_diabolical() {
+    check_level(Difficulty::Diabolical);
+}
@@ -0,0 +1,246 @@
+extern crate sudoku;
+
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use sudoku::grid::{Grid, Cell, Conflict, ParseError};
+use sudoku::solver::Unit;
+use sudoku::solver::step::candidates;
+
+const EASY: &str = "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+
+#[test]
+fn count_solutions_of_unique_puzzle() {
+    let grid = Grid::parse(EASY);
+    assert_eq!(grid.count_solutions(10), 1);
+    assert!(grid.has_unique_solution());
+}
+
+#[test]
+fn count_solutions_stops_at_limit() {
+    let grid = Grid::parse("");
+    assert_eq!(grid.count_solutions(0), 0);
+    assert_eq!(grid.count_solutions(5), 5);
+    assert!(!grid.has_unique_solution());
+}
+
+#[test]
+fn count_solutions_of_deadly_pattern() {
+    // a solved grid missing 9 and 2 in r2c1 and r3c8, 2 and 9 in r2c8 and
+    // r3c1, which can be swapped
+    let grid = Grid::parse("483921657.673458.1.518764.3548132976729564138136798245372689514814253769695417382");
+    assert_eq!(grid.count_solutions(10), 2);
+    assert!(!grid.has_unique_solution());
+}
+
+#[test]
+fn count_solutions_of_invalid_puzzle() {
+    assert_eq!(Grid::parse("11").count_solutions(10), 0);
+}
+
+#[test]
+fn validate_puzzle() {
+    assert_eq!(Grid::parse(EASY).validate(), Ok(()));
+}
+
+#[test]
+fn validate_duplicate() {
+    let conflicts = Grid::parse("1.......1").validate().unwrap_err();
+    let duplicate = Conflict::Duplicate { unit: Unit::Row(0), cells: (0, 8), value: 0 };
+    assert_eq!(conflicts, vec![duplicate]);
+    assert_eq!(duplicate.to_string(), "1 appears twice in row 1: r1c1 and r1c9");
+}
+
+#[test]
+fn validate_no_candidates() {
+    let grid = Grid::parse("12345678.........9");
+    assert_eq!(grid.validate(), Err(vec![Conflict::NoCandidates(8)]));
+    assert_eq!(Conflict::NoCandidates(8).to_string(), "No value is possible in r1c9");
+    assert_eq!(grid.count_solutions(1), 0);
+    assert!(grid.solve().is_none());
+}
+
+#[test]
+fn validate_after_editing() {
+    let mut grid = Grid::parse(EASY);
+    grid.values[0].value = 2;
+    grid.values[0].possible = 1 << 2;
+    assert!(grid.validate().is_err());
+    assert!(grid.solve().is_none());
+}
+
+#[test]
+fn try_parse_accepts_laid_out_grid() {
+    let laid_out = "\
+003|020|600
+900|305|001
+001|806|400
+---+---+---
+008|102|900
+700|000|008
+006|708|200
+---+---+---
+002|609|500
+800|203|009
+005|010|300";
+    assert_eq!(Grid::try_parse(laid_out), Ok(Grid::parse(EASY)));
+}
+
+#[test]
+fn try_parse_reports_invalid_character() {
+    let input = format!("{}x", &EASY[..12]);
+    assert_eq!(Grid::try_parse(&input),
+               Err(ParseError::InvalidCharacter { character: 'x', position: 12 }));
+    assert_eq!(Grid::try_parse("1 2 | #"),
+               Err(ParseError::InvalidCharacter { character: '#', position: 6 }));
+}
+
+#[test]
+fn try_parse_reports_wrong_length() {
+    assert_eq!(Grid::try_parse(""),
+               Err(ParseError::WrongLength { expected: 81, found: 0 }));
+    assert_eq!(Grid::try_parse(&EASY[..80]),
+               Err(ParseError::WrongLength { expected: 81, found: 80 }));
+    assert_eq!(Grid::try_parse(&format!("{}.", EASY)),
+               Err(ParseError::WrongLength { expected: 81, found: 82 }));
+}
+
+#[test]
+fn display_round_trip() {
+    let grid = Grid::parse(EASY);
+    let shown = grid.to_string();
+    assert_eq!(shown, EASY.replace('0', "."));
+    assert_eq!(shown.parse::<Grid>(), Ok(grid));
+
+    let solved = grid.solve().unwrap();
+    assert_eq!(solved.to_string().parse::<Grid>(), Ok(solved));
+}
+
+#[test]
+fn alternate_display() {
+    let grid = Grid::parse(EASY);
+    let table = [
+        "    3 |   2   | 6    ",
+        "9     | 3   5 |     1",
+        "    1 | 8   6 | 4    ",
+        "------+-------+------",
+        "    8 | 1   2 | 9    ",
+        "7     |       |     8",
+        "    6 | 7   8 | 2    ",
+        "------+-------+------",
+        "    2 | 6   9 | 5    ",
+        "8     | 2   3 |     9",
+        "    5 |   1   | 3    "
+    ];
+    assert_eq!(format!("{:#}", grid), table.join("\n"));
+    // without blanks the table reads back as a grid
+    let solved = grid.solve().unwrap();
+    assert_eq!(format!("{:#}", solved).parse::<Grid>(), Ok(solved));
+}
+
+#[test]
+fn cell_display_round_trip() {
+    let known = Cell { value: 4, possible: 1 << 4 };
+    let unknown = Cell { value: -1, possible: (1 << 9) - 1 };
+    assert_eq!(known.to_string(), "5");
+    assert_eq!(unknown.to_string(), ".");
+    assert_eq!("5".parse::<Cell>(), Ok(known));
+    assert_eq!(".".parse::<Cell>(), Ok(unknown));
+    assert_eq!("0".parse::<Cell>(), Ok(unknown));
+}
+
+#[test]
+fn cell_parse_errors() {
+    assert_eq!("".parse::<Cell>(), Err(ParseError::WrongLength { expected: 1, found: 0 }));
+    assert_eq!("12".parse::<Cell>(), Err(ParseError::WrongLength { expected: 1, found: 2 }));
+    assert_eq!("x".parse::<Cell>(),
+               Err(ParseError::InvalidCharacter { character: 'x', position: 0 }));
+}
+
+#[test]
+fn equal_grids_hash_the_same() {
+    let hash = |grid: &Grid| {
+        let mut hasher = DefaultHasher::new();
+        grid.hash(&mut hasher);
+        hasher.finish()
+    };
+    let grid = Grid::parse(EASY);
+    let same = EASY.replace('0', ".").parse::<Grid>().unwrap();
+    let solved = grid.solve().unwrap();
+    assert_eq!(grid, same);
+    assert_eq!(hash(&grid), hash(&same));
+    assert!(grid != solved);
+
+    let mut set = HashSet::new();
+    set.insert(grid);
+    set.insert(same);
+    set.insert(solved);
+    assert_eq!(set.len(), 2);
+}
+
+/// EASY as a pencilmark grid with box borders, the way HoDoKu writes it
+fn easy_pencilmarks() -> String {
+    let grid = Grid::parse(EASY);
+    let cands = candidates(&grid.values);
+    let border = ".-----------------------+-----------------------+-----------------------.";
+    let mut out = String::new();
+    for row in 0..9 {
+        if row % 3 == 0 {
+            out.push_str(border);
+            out.push('\n');
+        }
+        for col in 0..9 {
+            if col % 3 == 0 {
+                out.push_str("| ");
+            }
+            let i = row * 9 + col;
+            let digits: String = if grid.values[i].value == -1 {
+                (0..9).filter(|v| cands[i] & (1 << v) != 0)
+                    .map(|v| (b'1' + v as u8) as char)
+                    .collect()
+            } else {
+                (grid.values[i].value + 1).to_string()
+            };
+            out.push_str(&format!("{:<7}", digits));
+        }
+        out.push_str("|\n");
+    }
+    out.push_str(border);
+    out
+}
+
+#[test]
+fn parse_candidates_reads_pencilmarks() {
+    let grid = Grid::parse(EASY);
+    let cands = candidates(&grid.values);
+    let parsed = Grid::parse_candidates(&easy_pencilmarks()).unwrap();
+    for (i, cell) in parsed.values.iter().enumerate() {
+        let given = grid.values[i];
+        if given.value == -1 {
+            // a single candidate is read as the cell's value
+            assert_eq!(cell.possible, cands[i]);
+            assert_eq!(cell.value != -1, cands[i].count_ones() == 1);
+        } else {
+            assert_eq!(*cell, given);
+        }
+    }
+    assert_eq!(parsed.solve(), grid.solve());
+}
+
+#[test]
+fn parse_candidates_reports_invalid_character() {
+    assert_eq!(Grid::parse_candidates("12 3x4"),
+               Err(ParseError::InvalidCharacter { character: 'x', position: 4 }));
+    assert_eq!(Grid::parse_candidates("| 123 | 405"),
+               Err(ParseError::InvalidCharacter { character: '0', position: 9 }));
+}
+
+#[test]
+fn parse_candidates_reports_wrong_length() {
+    assert_eq!(Grid::parse_candidates(""),
+               Err(ParseError::WrongLength { expected: 81, found: 0 }));
+    let short = easy_pencilmarks().replacen("| 3      ", "| ", 1);
+    assert_eq!(Grid::parse_candidates(&short),
+               Err(ParseError::WrongLength { expected: 81, found: 80 }));
+}
@@ -0,0 +1,39 @@
+extern crate sudoku;
+
+use sudoku::grid::Grid;
+use sudoku::render::{self, Renderer};
+
+const EASY: &str = "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+
+/// The puzzle and its solution as the command line tool prints them
+fn cli_output(renderer: &dyn Renderer) -> String {
+    let grid = Grid::parse(EASY);
+    let solved = grid.solve().unwrap();
+    format!("{}\n\n{}\n\n", renderer.render(&grid), renderer.render(&solved))
+}
+
+#[test]
+fn small() {
+    assert_eq!(cli_output(&render::small), include_str!("render/small.txt"));
+}
+
+#[test]
+fn large() {
+    assert_eq!(cli_output(&render::large), include_str!("render/large.txt"));
+}
+
+#[test]
+fn oneline() {
+    assert_eq!(cli_output(&render::oneline), include_str!("render/oneline.txt"));
+}
+
+#[test]
+fn closure_renderer() {
+    let underscored = |grid: &Grid| render::oneline(grid).replace(' ', "_");
+    let grid = Grid::parse(EASY);
+    assert_eq!(underscored.render(&grid), EASY.replace('0', "_"));
+
+    let renderers: [&dyn Renderer; 2] = [&underscored, &render::oneline];
+    let solved = grid.solve().unwrap();
+    assert_eq!(renderers[0].render(&solved), renderers[1].render(&solved));
+}
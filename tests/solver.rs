@@ -0,0 +1,298 @@
+extern crate sudoku;
+
+use sudoku::grid::{Grid, Cell};
+use sudoku::solver::{Solver, Difficulty, Step, Technique, Event, Trace, Outcome, rate};
+use sudoku::solver::step::{self, TECHNIQUES};
+use sudoku::solver::naked;
+use sudoku::solver::strategy::{self, Strategy, StepResult};
+use sudoku::solver::rating::GUESS_WEIGHT;
+
+/// Puzzles with a unique solution, from the command line tool's demo and
+/// well known hard puzzles
+///
+/// `next_step` solves all but the last with X-Wing at most, the last gets
+/// nowhere. What they give is plenty of positions for every finder to look
+/// at.
+static PUZZLES: [&str; 9] = [
+    "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+    "043080250600000000000001094900004070000608000010200003820500000000000005034090710",
+    "000700000100000000000430200000000006000509000000000418000081000002000050040000300",
+    "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......",
+    "52...6.........7.13...........4..8..6......5...........418.........3..2...87.....",
+    "..............3.85..1.2.......5.7.....4...1...9.......5......73..2.1........4...9",
+    "000000010400000000020000000000050407008000300001090000300400200050100000000806000",
+    "9..8...........5............2..1...3.1.....6....4...7.7.86.........3.1..4.....2...",
+    "800000000003600000070090200050007000000045700000100030001000068008500010090000400"
+];
+
+fn solver() -> Solver {
+    Solver::new(Difficulty::Diabolical)
+}
+
+/// Naked singles under another name, to check that steps of user-defined
+/// strategies are reported as their own
+struct LastValue;
+
+impl Strategy for LastValue {
+    fn name(&self) -> &'static str {
+        "Last Value"
+    }
+
+    fn weight(&self) -> f32 {
+        1.0
+    }
+
+    fn apply(&self, cells: &mut [Cell; 81]) -> StepResult {
+        match self.find(&step::candidates(cells)) {
+            Some(step) => {
+                step.apply(cells);
+                StepResult::Changed
+            },
+            None => StepResult::Unchanged
+        }
+    }
+
+    fn find(&self, cands: &[u16; 81]) -> Option<Step> {
+        (0..81).find(|&i| cands[i].count_ones() == 1).map(|i| {
+            let mut step = Step::new(self.technique());
+            step.cells.push(i);
+            step.placed.push((i, cands[i].trailing_zeros() as i8));
+            step
+        })
+    }
+}
+
+/// Asserts that the step neither removes nor places anything contradicting
+/// the solution
+fn check_step(step: &Step, solution: &Grid) {
+    for &(i, v) in step.eliminated.iter() {
+        assert!(solution.values[i].value != v,
+                "removed a solution value: {}", step);
+    }
+    for &(i, v) in step.placed.iter() {
+        assert!(solution.values[i].value == v,
+                "placed a wrong value: {}", step);
+    }
+}
+
+/// Asserts that `solved` is the solution of `grid`
+fn check_solution(solved: &Grid, grid: &Grid) {
+    let solution = grid.solve().unwrap();
+    for i in 0..81 {
+        assert_eq!(solved.values[i].value, solution.values[i].value);
+    }
+}
+
+#[test]
+fn first_hint() {
+    let step = solver().next_step(&Grid::parse(PUZZLES[0])).unwrap();
+    assert_eq!(step.technique, Technique::NakedSingle);
+    assert_eq!(step.placed, vec![(41, 3)]);
+    assert_eq!(step.to_string(), "Naked Single: r5c6 = 4");
+}
+
+#[test]
+fn hints_solve_the_puzzles() {
+    for puzzle in PUZZLES[..8].iter() {
+        let puzzle = Grid::parse(puzzle);
+        let mut grid = puzzle;
+        while let Some(step) = solver().next_step(&grid) {
+            step.apply(&mut grid.values);
+        }
+        check_solution(&grid, &puzzle);
+    }
+}
+
+#[test]
+fn no_hint_for_a_solved_grid() {
+    let grid = Grid::parse(PUZZLES[0]).solve().unwrap();
+    assert_eq!(solver().next_step(&grid), None);
+}
+
+#[test]
+fn naked_pairs_after_a_full_row() {
+    // row 1 has nothing left to find, the pair in row 2 must still be used
+    let mut grid = Grid::parse("123456789");
+    grid.values[9].possible = 0b110;
+    grid.values[10].possible = 0b110;
+    grid.values[11].possible = 0b1110;
+    assert!(naked::rows(&mut grid.values));
+    assert_eq!(grid.values[11].possible & 0b110, 0);
+}
+
+#[test]
+fn builtin_steps_keep_the_solution() {
+    let strategies = strategy::builtin(Difficulty::Diabolical);
+    let solver = solver();
+    for puzzle in PUZZLES.iter() {
+        let mut grid = Grid::parse(puzzle);
+        let solution = grid.solve().unwrap();
+        // every finder at every position on the way to the solution
+        loop {
+            let cands = step::candidates(&grid.values);
+            for strategy in strategies.iter() {
+                if let Some(step) = strategy.find(&cands) {
+                    check_step(&step, &solution);
+                }
+            }
+            match solver.next_step(&grid) {
+                Some(step) => step.apply(&mut grid.values),
+                None => break
+            }
+        }
+    }
+}
+
+#[test]
+fn hidden_singles_alone() {
+    let puzzle = Grid::parse(PUZZLES[0]);
+    let mut grid = puzzle;
+    let solver = Solver::with_techniques(&[Technique::HiddenSingle]);
+    assert!(solver.solve_mut(&mut grid.values).is_solved());
+    check_solution(&grid, &puzzle);
+}
+
+#[test]
+fn techniques_found_by_another_finder() {
+    let reported = [
+        Technique::SashimiXWing,
+        Technique::SashimiSwordfish,
+        Technique::UniqueRectangle5,
+        Technique::GroupedAic
+    ];
+    for &technique in reported.iter() {
+        assert_eq!(Solver::with_techniques(&[technique]).strategies.len(), 1,
+                   "{} has no strategy", technique);
+    }
+}
+
+#[test]
+fn stuck_with_the_candidates_left() {
+    let mut grid = Grid::parse(PUZZLES[8]);
+    let solver = Solver::with_techniques(&[Technique::NakedSingle]);
+    match solver.solve_mut(&mut grid.values) {
+        Outcome::Stuck(cands) => {
+            assert_eq!(cands, step::candidates(&grid.values));
+            assert!(cands.iter().all(|&c| c.count_ones() != 1));
+        },
+        outcome => panic!("expected to get stuck, got {:?}", outcome)
+    }
+}
+
+#[test]
+fn contradiction() {
+    let mut grid = Grid::parse("11");
+    let outcome = Solver::new(Difficulty::Diabolical).solve_mut(&mut grid.values);
+    assert_eq!(outcome, Outcome::Contradiction);
+}
+
+#[test]
+fn techniques_listed_in_solver_order() {
+    let names: Vec<&str> = TECHNIQUES.iter().map(|t| t.name()).collect();
+    let mut last = 0;
+    for strategy in strategy::builtin(Difficulty::Diabolical).iter() {
+        let n = names.iter().position(|&name| name == strategy.name()).unwrap();
+        assert!(n >= last, "{} is listed too early", strategy.name());
+        last = n;
+    }
+}
+
+#[test]
+fn custom_strategy() {
+    let technique = Technique::Custom("Last Value", 1.0);
+    let solver = Solver::with_strategies(vec![Box::new(LastValue)]);
+    let puzzle = Grid::parse(PUZZLES[0]);
+
+    let step = solver.next_step(&puzzle).unwrap();
+    assert_eq!(step.technique, technique);
+    assert_eq!(step.to_string(), "Last Value: r5c6 = 4");
+
+    let mut grid = puzzle;
+    let mut trace = Trace::new();
+    assert!(solver.solve_traced(&mut grid.values, &mut trace));
+    check_solution(&grid, &puzzle);
+    assert_eq!(trace.count(technique), trace.events.len());
+    assert_eq!(trace.counts.get("Last Value"), Some(&trace.events.len()));
+
+    let rating = solver.rate(&puzzle);
+    assert!(rating.solved);
+    assert_eq!(rating.hardest, Some(technique));
+    assert_eq!(rating.score, 1.0);
+    assert_eq!(rating.steps, trace.events.len());
+}
+
+#[test]
+fn trace_without_guessing() {
+    let grid = Grid::parse(PUZZLES[0]);
+    let (solution, trace) = grid.solve_traced();
+    check_solution(&solution.unwrap(), &grid);
+    assert!(trace.is_logical());
+    assert_eq!(trace.max_depth, 0);
+    assert!(trace.count(Technique::NakedSingle) > 0);
+    let total: usize = trace.counts.values().sum();
+    assert_eq!(total, trace.events.len());
+}
+
+#[test]
+fn trace_with_guesses() {
+    let grid = Grid::parse(PUZZLES[8]);
+    let (solution, trace) = grid.solve_traced();
+    check_solution(&solution.unwrap(), &grid);
+    assert!(!trace.is_logical());
+
+    let guesses: Vec<(usize, bool)> = trace.events.iter()
+        .filter_map(|event| match *event {
+            Event::Guess { depth, backtracked, .. } => Some((depth, backtracked)),
+            Event::Step(_) => None
+        })
+        .collect();
+    assert_eq!(guesses.len(), trace.guesses);
+    assert_eq!(guesses[0].0, 1);
+    assert!(guesses.iter().all(|&(depth, _)| depth >= 1 && depth <= trace.max_depth));
+    assert!(guesses.iter().any(|&(depth, _)| depth == trace.max_depth));
+    assert!(guesses.iter().any(|&(_, backtracked)| backtracked));
+    // the guesses kept are the path to the solution, one at each depth
+    let kept: Vec<usize> = guesses.iter()
+        .filter(|&&(_, backtracked)| !backtracked)
+        .map(|&(depth, _)| depth)
+        .collect();
+    assert_eq!(kept, (1..kept.len() + 1).collect::<Vec<_>>());
+}
+
+#[test]
+fn trace_of_an_empty_grid() {
+    let grid = Grid::parse("");
+    let (solution, trace) = grid.solve_traced();
+    assert!(solution.is_some());
+    assert!(trace.guesses > 0);
+}
+
+#[test]
+fn trace_of_an_invalid_grid() {
+    let (solution, trace) = Grid::parse("11").solve_traced();
+    assert!(solution.is_none());
+    assert!(trace.events.is_empty());
+}
+
+#[test]
+fn rate_easy_puzzle() {
+    let rating = rate(&Grid::parse(PUZZLES[0]));
+    assert!(rating.solved);
+    assert_eq!(rating.guesses, 0);
+    assert_eq!(rating.hardest, Some(Technique::HiddenSingle));
+    assert_eq!(rating.score, Technique::HiddenSingle.weight());
+}
+
+#[test]
+fn rate_puzzle_needing_a_guess() {
+    let rating = rate(&Grid::parse(PUZZLES[8]));
+    assert!(rating.solved);
+    assert!(rating.guesses > 0);
+    assert_eq!(rating.hardest, None);
+    assert_eq!(rating.score, GUESS_WEIGHT);
+}
+
+#[test]
+fn rate_invalid_puzzle() {
+    assert!(!rate(&Grid::parse("11")).solved);
+}
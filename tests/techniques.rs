@@ -0,0 +1,759 @@
+extern crate sudoku;
+
+use sudoku::grid::Grid;
+use sudoku::solver::{Step, Technique};
+use sudoku::solver::step::candidates;
+use sudoku::solver::{fish, naked, hidden, wings, single_digit, coloring, medusa, chains, unique, als, locked, forcing};
+
+/// Candidates of a grid written as pencilmarks, one row per line with the
+/// possible values of each cell, a single value being a known cell
+fn pencilmarks(rows: [&str; 9]) -> [u16; 81] {
+    let grid = Grid::parse_candidates(&rows.join("\n")).unwrap();
+    candidates(&grid.values)
+}
+
+/// Asserts that the finder found the technique with exactly these
+/// eliminations and placements
+fn check(step: Option<Step>, technique: Technique,
+         eliminated: &[(usize, i8)], placed: &[(usize, i8)]) {
+    let step = step.expect("no step found");
+    assert_eq!(step.technique, technique, "{}", step);
+    assert_eq!(step.eliminated, eliminated, "{}", step);
+    assert_eq!(step.placed, placed, "{}", step);
+}
+
+#[test]
+fn x_wing() {
+    // X-Wing in column 4, column 9, row 1, row 9: r1c5 <> 3 r9c5 <> 3
+    let cands = pencilmarks([
+        "7    9    8    23   234  1    6    5    34",
+        "134  134  2    6    5    34   9    8    7",
+        "345  6    45   9    8    7    34   2    1",
+        "8    15   9    4    123  23   7    6    25",
+        "15   7    6    12   9    8    24   3    245",
+        "2    34   34   7    6    5    1    9    8",
+        "9    23   1    5    24   234  8    7    6",
+        "345  2345 345  8    7    6    23   1    9",
+        "6    8    7    123  13   9    5    4    23"
+    ]);
+    check(fish::x_wing(&cands), Technique::XWing,
+          &[(4, 2), (76, 2)], &[]);
+}
+
+#[test]
+fn swordfish() {
+    // Swordfish in row 3, row 5, row 9, column 1, column 5, column 6:
+    // r8c1 <> 9
+    let cands = pencilmarks([
+        "2   13  13  5   4   6   9   7   8",
+        "8   7   59  29  1   3   6   4   25",
+        "459 45  6   8   7   29  3   1   25",
+        "3   24  24  6   5   7   1   8   9",
+        "159 8   15  3   29  4   7   25  6",
+        "6   59  7   29  8   1   4   25  3",
+        "45  345 345 7   6   8   2   9   1",
+        "19  129 129 4   3   5   8   6   7",
+        "7   6   8   1   29  29  5   3   4"
+    ]);
+    check(fish::swordfish(&cands), Technique::Swordfish,
+          &[(63, 8)], &[]);
+}
+
+#[test]
+fn jellyfish() {
+    // Jellyfish in row 3, row 5, row 8, row 9, column 1, column 5, column 6,
+    // column 7: r7c1 <> 3 r1c5 <> 3 r1c6 <> 3 r4c7 <> 3
+    let cands = pencilmarks([
+        "9    278  347  13   1234 234  478  5    6",
+        "6    5    34   9    7    8    1    24   23",
+        "3478 278  1    6    234  5    3478 478  9",
+        "257  6    257  137  8    9    235  13   4",
+        "15   9    8    4    123  23   35   6    7",
+        "1247 3    247  17   5    6    28   9    18",
+        "235  4    235  8    6    7    9    13   123",
+        "278  1    9    5    34   34   6    278  28",
+        "378  78   6    2    9    1    3478 478  5"
+    ]);
+    check(fish::jellyfish(&cands), Technique::Jellyfish,
+          &[(54, 2), (4, 2), (5, 2), (33, 2)], &[]);
+}
+
+#[test]
+fn finned_x_wing() {
+    // Finned X-Wing in column 1, column 7, row 3, row 8, box 1: r3c3 <> 3
+    let cands = pencilmarks([
+        "7    9    348  235  2345 1    6    58   34",
+        "1348 134  2    6    345  34   9    58   7",
+        "345  456  3456 9    8    7    34   2    1",
+        "158  156  9    4    123  23   78   67   2568",
+        "145  7    456  12   9    8    24   3    2456",
+        "2    34   348  7    6    5    1    9    48",
+        "9    238  1    235  2345 234  78   67   2368",
+        "345  2345 345  8    7    6    23   1    9",
+        "6    238  7    123  123  9    5    4    238"
+    ]);
+    check(fish::finned_x_wing(&cands), Technique::FinnedXWing,
+          &[(20, 2)], &[]);
+}
+
+#[test]
+fn sashimi_x_wing() {
+    // Sashimi X-Wing in column 3, column 9, row 5, row 7, box 7: r7c1 <> 1
+    let cands = pencilmarks([
+        "7    9    8    1345 2    134  34   35   6",
+        "4    6    35   9    8    37   1    2357 23",
+        "135  13   2    6    345  347  47   8    9",
+        "8    13   9    1234 134  1234 5    6    7",
+        "2    4    13   7    6    5    8    9    13",
+        "56   7    56   13   9    8    2    13   4",
+        "13   5    4    8    7    6    9    123  123",
+        "139  2    137  1345 1345 1349 6    137  8",
+        "1369 8    1367 123  13   129  37   4    5"
+    ]);
+    check(fish::finned_x_wing(&cands), Technique::SashimiXWing,
+          &[(54, 0)], &[]);
+}
+
+#[test]
+fn finned_swordfish() {
+    // Finned Swordfish in row 2, row 4, row 8, column 2, column 5, column 9,
+    // box 3: r3c9 <> 4
+    let cands = pencilmarks([
+        "2     138   138   4     5     36    7     89    13689",
+        "5     1348  6     7     28    9     148   248   12348",
+        "348   7     9     1     28    36    48    5     23468",
+        "6     5     7     89    49    1     2     3     48",
+        "348   2     348   5     6     7     1489  489   1489",
+        "9     148   148   28    3     248   5     6     7",
+        "348   3489  5     6     7     248   489   1     2489",
+        "1     489   2     3     49    5     6     7     489",
+        "7     6     48    289   1     248   3     2489  5"
+    ]);
+    check(fish::finned_swordfish(&cands), Technique::FinnedSwordfish,
+          &[(26, 3)], &[]);
+}
+
+#[test]
+fn sashimi_swordfish() {
+    // Sashimi Swordfish in row 1, row 5, row 7, column 3, column 5, column 9,
+    // box 3: r3c9 <> 6
+    let cands = pencilmarks([
+        "1     38    2     4     36    5     9     68    7",
+        "4     6     5     79    79    8     3     2     1",
+        "78    389   3789  16    136   2     46    5     468",
+        "268   4     38    5     1267  1367  126   9     268",
+        "5     7     16    8     126   9     124   3     246",
+        "268   12389 389   126   4     136   7     68    5",
+        "9     12    16    3     5     4     8     7     26",
+        "2678  28    78    1269  1269  16    5     4     3",
+        "3     5     4     267   8     67    26    1     9"
+    ]);
+    check(fish::finned_swordfish(&cands), Technique::SashimiSwordfish,
+          &[(26, 5)], &[]);
+}
+
+#[test]
+fn naked_triple() {
+    // Naked Triple in column 1: r4c1 <> 8 r6c1 <> 5 r6c1 <> 7 r7c1 <> 5
+    let cands = pencilmarks([
+        "2      3      1      6      4      59     7      59     8",
+        "578    567    45678  3579   57     3589   146    134569 2",
+        "578    9      45678  2357   1      2358   46     3456   356",
+        "1389   12     289    1349   26     3469   5      7      136",
+        "6      1257   2579   13579  8      359    124    134    13",
+        "1357   4      257    1357   2567   356    1268   1368   9",
+        "159    1256   2569   245    3      2456   168    1568   7",
+        "57     8      2567   25     9      1      3      56     4",
+        "4      156    3      8      56     7      9      2      156"
+    ]);
+    check(naked::find_triple(&cands), Technique::NakedTriple,
+          &[(27, 7), (45, 4), (45, 6), (54, 4)], &[]);
+}
+
+#[test]
+fn naked_quad() {
+    // Naked Quad in box 1: r1c3 <> 2 r1c3 <> 5 r1c3 <> 9 r2c1 <> 2 r2c3 <> 2
+    // r2c3 <> 5 r3c1 <> 2 r3c1 <> 8
+    let cands = pencilmarks([
+        "4      259    12569  569    8      257    3      1267   126",
+        "12367  25     12356  456    245    2457   9      12467  8",
+        "2678   289    289    3      249    1      26     2467   5",
+        "5      6      7      19     239    8      4      1239   1239",
+        "128    2489   12489  1459   23459  2345   7      123569 12369",
+        "12     3      1249   7      6      245    125    8      129",
+        "368    7      34568  2      1      9      56     356    346",
+        "23     245    2345   8      7      6      125    12359  12349",
+        "9      1      26     45     345    345    8      26     7"
+    ]);
+    check(naked::find_quad(&cands), Technique::NakedQuad,
+          &[(2, 1), (2, 4), (2, 8), (9, 1), (11, 1), (11, 4), (18, 1), (18, 7)], &[]);
+}
+
+#[test]
+fn hidden_pair() {
+    // Hidden Pair in column 7: r4c7 <> 2 r7c7 <> 2 r7c7 <> 3
+    let cands = pencilmarks([
+        "7    9    348  235  2345 1    6    58   34",
+        "1348 134  2    6    345  34   9    58   7",
+        "345  3456 3456 9    8    7    34   2    1",
+        "158  156  9    4    123  23   278  67   2568",
+        "145  7    456  12   9    8    24   3    2456",
+        "2    34   348  7    6    5    1    9    48",
+        "9    238  1    235  2345 234  2378 67   2368",
+        "345  2345 345  8    7    6    23   1    9",
+        "6    238  7    123  123  9    5    4    238"
+    ]);
+    check(hidden::find_pair(&cands), Technique::HiddenPair,
+          &[(33, 1), (60, 1), (60, 2)], &[]);
+}
+
+#[test]
+fn hidden_triple() {
+    // Hidden Triple in row 8: r8c2 <> 4 r8c2 <> 5 r8c3 <> 4 r8c3 <> 5
+    // r8c3 <> 7 r8c7 <> 7
+    let cands = pencilmarks([
+        "5      2469   2469   347    8      3679   1      27     39",
+        "2469   1      3      457    2457   5679   47     8      59",
+        "8      7      249    1      234    359    34     25     6",
+        "679    569    5679   8      579    1      2      3      4",
+        "39     8      1      2      39     4      5      6      7",
+        "2347   245    247    357    6      357    8      9      1",
+        "479    3      4579   6      457    8      79     1      2",
+        "1      24569  245679 3457   3457   357    679    57     8",
+        "67     56     8      9      1      2      367    4      35"
+    ]);
+    check(hidden::find_triple(&cands), Technique::HiddenTriple,
+          &[(64, 3), (64, 4), (65, 3), (65, 4), (65, 6), (69, 6)], &[]);
+}
+
+#[test]
+fn hidden_quad() {
+    // Hidden Quad in row 7: r7c1 <> 9 r7c2 <> 9 r7c3 <> 8 r7c3 <> 9 r7c9 <> 7
+    // r7c9 <> 8 r7c9 <> 9
+    let cands = pencilmarks([
+        "2       1357    135     3567    4       1367    9       578     578",
+        "8       134579  13459   23579   1279    12379   6       2457    257",
+        "459     4579    6       8       279     279     3       1       257",
+        "3       12469   1249    2679    5       1246789 18      289     1289",
+        "1459    8       12459   239     129     12349   7       259     6",
+        "1569    12569   7       2369    1289    123689  4       2589    123589",
+        "1459    13459   134589  79      6       789     2       789     1789",
+        "169     1269    1289    4       3       5       18      6789    1789",
+        "7       269     289     1       289     289     5       3       4"
+    ]);
+    check(hidden::find_quad(&cands), Technique::HiddenQuad,
+          &[(54, 8), (55, 8), (56, 7), (56, 8), (62, 6), (62, 7), (62, 8)], &[]);
+}
+
+#[test]
+fn xy_wing() {
+    // XY-Wing: r9c2 <> 6
+    let cands = pencilmarks([
+        "2     3     1     6     4     59    7     59    8",
+        "578   67    467   3579  57    3589  146   13459 2",
+        "58    9     4567  2357  1     2358  46    3456  356",
+        "139   12    8     1349  26    3469  5     7     136",
+        "6     1257  2579  1359  8     359   124   134   13",
+        "13    4     25    1357  2567  356   1268  1368  9",
+        "19    1256  269   245   3     2456  168   1568  7",
+        "57    8     2567  25    9     1     3     56    4",
+        "4     156   3     8     56    7     9     2     156"
+    ]);
+    check(wings::xy_wing(&cands), Technique::XYWing,
+          &[(73, 5)], &[]);
+}
+
+#[test]
+fn xyz_wing() {
+    // XYZ-Wing: r5c3 <> 4
+    let cands = pencilmarks([
+        "7    9    48   235  2345 1    6    58   34",
+        "1348 134  2    6    345  34   9    58   7",
+        "345  456  456  9    8    7    34   2    1",
+        "158  156  9    4    123  23   78   67   2568",
+        "145  7    456  12   9    8    24   3    2456",
+        "2    34   348  7    6    5    1    9    48",
+        "9    238  1    235  2345 234  78   67   2368",
+        "345  2345 345  8    7    6    23   1    9",
+        "6    238  7    123  123  9    5    4    238"
+    ]);
+    check(wings::xyz_wing(&cands), Technique::XYZWing,
+          &[(38, 3)], &[]);
+}
+
+#[test]
+fn w_wing() {
+    // W-Wing in box 7: r3c1 <> 9
+    let cands = pencilmarks([
+        "2   1   3   5   4   6   9   7   8",
+        "8   7   59  29  1   3   6   4   25",
+        "459 45  6   8   7   29  3   1   25",
+        "3   24  24  6   5   7   1   8   9",
+        "59  8   1   3   29  4   7   25  6",
+        "6   59  7   29  8   1   4   25  3",
+        "45  3   45  7   6   8   2   9   1",
+        "1   29  29  4   3   5   8   6   7",
+        "7   6   8   1   29  29  5   3   4"
+    ]);
+    check(wings::w_wing(&cands), Technique::WWing,
+          &[(18, 8)], &[]);
+}
+
+#[test]
+fn skyscraper() {
+    // Skyscraper in row 2, row 6: r3c2 <> 9 r5c3 <> 9
+    let cands = pencilmarks([
+        "2   13  13  5   4   6   9   7   8",
+        "8   7   59  29  1   3   6   4   25",
+        "459 459 6   8   7   29  3   1   25",
+        "3   24  24  6   5   7   1   8   9",
+        "159 8   159 3   29  4   7   25  6",
+        "6   59  7   29  8   1   4   25  3",
+        "45  345 345 7   6   8   2   9   1",
+        "19  129 129 4   3   5   8   6   7",
+        "7   6   8   1   29  29  5   3   4"
+    ]);
+    check(single_digit::skyscraper(&cands), Technique::Skyscraper,
+          &[(19, 8), (38, 8)], &[]);
+}
+
+#[test]
+fn two_string_kite() {
+    // 2-String Kite in row 9, column 1, box 7: r5c9 <> 2
+    let cands = pencilmarks([
+        "4    6    5    89   89   7    1    23   23",
+        "1    3    2    5    6    4    7    9    8",
+        "7    9    8    2    3    1    4    6    5",
+        "8    1    9    3    4    2    5    7    6",
+        "23   247  347  6    179  5    8    12   129",
+        "5    27   6    179  179  8    23   4    1239",
+        "23   5    347  478  278  6    9    123  1237",
+        "6    8    37   17   127  9    23   5    4",
+        "9    247  1    47   5    3    6    8    27"
+    ]);
+    check(single_digit::two_string_kite(&cands), Technique::TwoStringKite,
+          &[(44, 1)], &[]);
+}
+
+#[test]
+fn empty_rectangle() {
+    // Empty Rectangle in box 7, column 7: r3c2 <> 3
+    let cands = pencilmarks([
+        "7    9    348  235  2345 1    6    58   34",
+        "1348 134  2    6    345  34   9    58   7",
+        "345  3456 3456 9    8    7    34   2    1",
+        "158  156  9    4    123  23   78   67   2568",
+        "145  7    456  12   9    8    24   3    2456",
+        "2    34   348  7    6    5    1    9    48",
+        "9    238  1    235  2345 234  78   67   2368",
+        "345  2345 345  8    7    6    23   1    9",
+        "6    238  7    123  123  9    5    4    238"
+    ]);
+    check(single_digit::empty_rectangle(&cands), Technique::EmptyRectangle,
+          &[(19, 2)], &[]);
+}
+
+#[test]
+fn simple_coloring() {
+    // Simple Coloring: r1c2 <> 8 r7c2 <> 8, colors [8r4c4 8r8c9 8r6c2 | 8r9c4
+    // 8r4c9 8r8c2 8r7c7 8r6c6]
+    let cands = pencilmarks([
+        "2    138  38   4    5    36   7    89   169",
+        "5    14   6    7    28   9    14   28   3",
+        "348  7    9    1    28   36   48   5    26",
+        "6    5    7    89   49   1    2    3    48",
+        "38   2    348  5    6    7    189  489  19",
+        "9    48   1    2    3    48   5    6    7",
+        "348  3489 5    6    7    248  89   1    29",
+        "1    89   2    3    49   5    6    7    48",
+        "7    6    48   89   1    28   3    249  5"
+    ]);
+    check(coloring::simple(&cands), Technique::SimpleColoring,
+          &[(1, 7), (55, 7)], &[]);
+}
+
+#[test]
+fn multi_coloring() {
+    // Multi-Coloring: r7c6 <> 2, colors [2r1c2 2r6c8 2r2c7 | 2r1c8 2r2c6 |
+    // 2r6c5 | 2r8c5]
+    let cands = pencilmarks([
+        "1     27    3     5     6     4     8     27    9",
+        "4     5     6     8     9     27    23    1     37",
+        "279   8     279   12    3     127   5     4     6",
+        "8     129   1279  1239  4     123   6     5     37",
+        "269   3     4     269   7     5     29    8     1",
+        "5     12679 1279  12369 12    8     239   27    4",
+        "239   129   129   4     5     123   7     6     8",
+        "267   1267  8     127   12    9     4     3     5",
+        "37    4     5     37    8     6     1     9     2"
+    ]);
+    check(coloring::multi(&cands), Technique::MultiColoring,
+          &[(59, 1)], &[]);
+}
+
+#[test]
+fn medusa() {
+    // 3D Medusa: r6c4 <> 5, colors [5r1c6 9r1c8 7r2c5 7r6c4 | 9r1c6 5r1c8
+    // 5r2c5 9r2c8 5r5c6 7r6c5]
+    let cands = pencilmarks([
+        "2     3     1     6     4     59    7     59    8",
+        "58    67    467   379   57    389   146   13459 2",
+        "58    9     467   237   1     238   46    3456  356",
+        "139   12    8     1349  26    3469  5     7     136",
+        "6     127   2579  1359  8     359   124   134   13",
+        "13    4     25    1357  257   36    1268  1368  9",
+        "19    1256  269   245   3     246   168   1568  7",
+        "7     8     26    25    9     1     3     56    4",
+        "4     15    3     8     56    7     9     2     156"
+    ]);
+    check(medusa::find(&cands), Technique::Medusa,
+          &[(48, 4)], &[]);
+}
+
+#[test]
+fn x_cycle() {
+    // X-Cycle: r4c2 = 9, chain 9r4c2 = 9r4c3 - 9r7c3 = 9r7c7 - 9r3c7 = 9r3c6
+    // - 9r8c6 = 9r9c5 - 9r9c2 = 9r4c2
+    let cands = pencilmarks([
+        "3468 5    3468 89   1389 7    139  2    149",
+        "9    348  7    2    138  148  6    5    14",
+        "34   2    1    5    6    49   39   8    7",
+        "1    89   89   3    4    2    7    6    5",
+        "7    6    5    89   189  189  4    3    2",
+        "234  34   234  6    7    5    18   9    18",
+        "28   1    289  4    5    3    89   7    6",
+        "368  7    369  1    2    89   5    4    39",
+        "5    3489 349  7    89   6    2    1    389"
+    ]);
+    check(chains::x_cycle(&cands), Technique::XCycle,
+          &[], &[(28, 8)]);
+}
+
+#[test]
+fn xy_chain() {
+    // XY-Chain: r6c6 <> 5, chain 5r1c6 = 9r1c6 - 9r1c8 = 5r1c8 -
+    // 5r8c8 = 6r8c8 - 6r8c3 = 2r8c3 - 2r6c3 = 5r6c3
+    let cands = pencilmarks([
+        "2     3     1     6     4     59    7     59    8",
+        "58    67    467   379   57    389   146   13459 2",
+        "58    9     467   237   1     238   46    3456  356",
+        "139   12    8     1349  26    3469  5     7     136",
+        "6     127   2579  1359  8     359   124   134   13",
+        "13    4     25    1357  257   356   1268  1368  9",
+        "19    1256  269   245   3     246   168   1568  7",
+        "7     8     26    25    9     1     3     56    4",
+        "4     15    3     8     56    7     9     2     156"
+    ]);
+    check(chains::xy_chain(&cands), Technique::XYChain,
+          &[(50, 4)], &[]);
+}
+
+#[test]
+fn aic() {
+    // AIC: r3c7 <> 3, chain 2r3c7 = 2r8c7 - 1r8c7 = 1r7c9 - 1r3c9 = 3r3c9
+    let cands = pencilmarks([
+        "14    3     2     5     49    6     189   79    178",
+        "79    789   78    2     1     3     5     6     4",
+        "14    6     5     8     7     49    1239  239   13",
+        "579   579   6     39    3589  1     389   4     2",
+        "8     1459  19    34    2     4579  6     3579  357",
+        "2     459   3     6     4589  45789 89    1     578",
+        "6     15789 78    19    589   2     4     35    135",
+        "3     158   4     7     6     58    12    25    9",
+        "59    2     19    1349  345   459   7     8     6"
+    ]);
+    check(chains::aic(&cands), Technique::Aic,
+          &[(24, 2)], &[]);
+}
+
+#[test]
+fn grouped_aic() {
+    // Grouped AIC: r2c3 <> 5, chain 5r2c5 = 7r2c5 - 7r2c2 = 7r5c2 -
+    // 5r5c2 = 5{r5c3 r6c3}
+    let cands = pencilmarks([
+        "2     3     1     6     4     59    7     59    8",
+        "578   567   4567  3579  57    3589  146   13459 2",
+        "578   9     4567  2357  1     2358  46    3456  356",
+        "139   12    8     1349  26    3469  5     7     136",
+        "6     1257  2579  1359  8     359   124   134   13",
+        "13    4     25    1357  2567  356   1268  1368  9",
+        "19    1256  2569  245   3     2456  168   1568  7",
+        "57    8     2567  25    9     1     3     56    4",
+        "4     156   3     8     56    7     9     2     156"
+    ]);
+    check(chains::aic(&cands), Technique::GroupedAic,
+          &[(11, 4)], &[]);
+}
+
+#[test]
+fn unique_rectangle_1() {
+    // Unique Rectangle Type 1: r7c5 <> 1 r7c5 <> 3
+    let cands = pencilmarks([
+        "36   1    24   5    68   47   379  789  2789",
+        "9    7    8    2    13   13   6    4    5",
+        "56   34   25   68   9    47   37   1    28",
+        "7    5    6    19   14   8    249  29   3",
+        "4    2    3    679  67   5    1    789  789",
+        "1    8    9    3    47   2    47   5    6",
+        "2    39   157  4    135  13   8    6    79",
+        "8    6    17   17   2    9    5    3    4",
+        "35   349  457  78   3578 6    27   279  1"
+    ]);
+    check(unique::type_1(&cands), Technique::UniqueRectangle1,
+          &[(58, 0), (58, 2)], &[]);
+}
+
+#[test]
+fn unique_rectangle_2() {
+    // Unique Rectangle Type 2: r4c1 <> 8 r5c9 <> 8
+    let cands = pencilmarks([
+        "34    2     1     5     6     347   3789  79    789",
+        "6     57    45    8     9     347   137   137   2",
+        "389   789   378   12    23    137   4     6     5",
+        "1489  3     2     6     7     5     189   149   189",
+        "189   1689  68    3     4     2     5     179   16789",
+        "7     56    45    9     1     8     2     34    36",
+        "2     17    9     4     5     13    6     8     37",
+        "138   1678  3678  12    238   9     37    5     4",
+        "5     4     38    7     38    6     19    2     19"
+    ]);
+    check(unique::type_2(&cands), Technique::UniqueRectangle2,
+          &[(27, 7), (44, 7)], &[]);
+}
+
+#[test]
+fn unique_rectangle_3() {
+    // Unique Rectangle Type 3 in row 2: r2c5 <> 3
+    let cands = pencilmarks([
+        "1    3    2    5    4    6    7    8    9",
+        "49   6    5    378  378  3789 1    2    34",
+        "7    49   8    2    1    39   3456 356  3456",
+        "2    479  39   6    357  3457 8    359  1",
+        "589  1489 139  348  2    3458 3456 3569 7",
+        "458  478  6    9    3578 1    2    35   345",
+        "69   2    19   134  35   345  36   7    8",
+        "68   18   7    13   9    2    356  4    356",
+        "3    5    4    78   6    78   9    1    2"
+    ]);
+    check(unique::type_3(&cands), Technique::UniqueRectangle3,
+          &[(13, 2)], &[]);
+}
+
+#[test]
+fn unique_rectangle_4() {
+    // Unique Rectangle Type 4 in column 6: r2c6 <> 5 r3c6 <> 5
+    let cands = pencilmarks([
+        "2     3     1     6     4     59    7     59    8",
+        "58    67    467   379   57    3589  146   13459 2",
+        "58    9     467   237   1     2358  46    3456  356",
+        "139   12    8     1349  26    3469  5     7     136",
+        "6     127   2579  1359  8     359   124   134   13",
+        "13    4     25    1357  257   356   1268  1368  9",
+        "19    1256  269   245   3     246   168   1568  7",
+        "7     8     26    25    9     1     3     56    4",
+        "4     15    3     8     56    7     9     2     156"
+    ]);
+    check(unique::type_4(&cands), Technique::UniqueRectangle4,
+          &[(14, 4), (23, 4)], &[]);
+}
+
+#[test]
+fn unique_rectangle_5() {
+    // Unique Rectangle Type 5: r1c2 <> 7
+    let cands = pencilmarks([
+        "1579 5679 8    17   3    2    4    56   169",
+        "4    567  567  17   9    8    126  256  3",
+        "19   3    2    456  46   45   7    8    19",
+        "8    1    9    24   47   3    5    267  267",
+        "257  567  56   8    157  9    12   3    4",
+        "2357 457  34   25   17   6    8    9    127",
+        "579  2    1    3    456  45   69   67   8",
+        "6    8    57   9    2    1    3    4    57",
+        "359  459  34   56   8    7    269  1    256"
+    ]);
+    check(unique::type_2(&cands), Technique::UniqueRectangle5,
+          &[(1, 6)], &[]);
+}
+
+#[test]
+fn unique_rectangle_6() {
+    // Unique Rectangle Type 6 in row 5, row 6: r5c9 <> 9 r6c3 <> 9
+    let cands = pencilmarks([
+        "36    1     2     5     46    346   7     9     8",
+        "56    4567  4567  8     27    9     1     3     26",
+        "9     367   8     27    1     36    4     26    5",
+        "7     56    56    9     8     1     2     4     3",
+        "12    8     19    3     247   47    5     167   169",
+        "4     23    139   6     5     27    8     17    19",
+        "356   34567 34567 47    67    8     9     12    12",
+        "8     267   67    1     9     267   3     5     4",
+        "12    9     14    24    3     5     6     8     7"
+    ]);
+    check(unique::type_6(&cands), Technique::UniqueRectangle6,
+          &[(44, 8), (47, 8)], &[]);
+}
+
+#[test]
+fn hidden_unique_rectangle() {
+    // Hidden Unique Rectangle in row 9, column 5: r9c5 <> 9
+    let cands = pencilmarks([
+        "14    3     2     5     49    6     189   79    178",
+        "79    789   78    2     1     3     5     6     4",
+        "14    6     5     8     7     49    1239  239   13",
+        "579   579   6     39    3589  1     389   4     2",
+        "8     1459  19    34    2     4579  6     3579  357",
+        "2     459   3     6     4589  45789 89    1     578",
+        "6     15789 78    19    589   2     4     35    135",
+        "3     158   4     7     6     58    12    25    9",
+        "59    2     19    1349  3459  459   7     8     6"
+    ]);
+    check(unique::hidden(&cands), Technique::HiddenUniqueRectangle,
+          &[(76, 8)], &[]);
+}
+
+#[test]
+fn bug_plus_one() {
+    // BUG+1: r2c3 = 3
+    let cands = pencilmarks([
+        "5   24  46  9   7   68  3   28  1",
+        "23  1   369 68  45  35  49  28  7",
+        "8   7   39  34  12  12  6   5   49",
+        "23  24  34  7   56  56  1   9   8",
+        "9   8   1   34  24  23  7   6   5",
+        "6   5   7   1   89  89  24  3   24",
+        "1   9   2   5   3   4   8   7   6",
+        "7   6   8   2   19  19  5   4   3",
+        "4   3   5   68  68  7   29  1   29"
+    ]);
+    check(unique::bug(&cands), Technique::BugPlusOne,
+          &[], &[(11, 2)]);
+}
+
+#[test]
+fn als_xz() {
+    // ALS-XZ in box 7, column 4: r5c1 <> 6
+    let cands = pencilmarks([
+        "1    27   3    5    6    4    8    27   9",
+        "4    5    6    8    9    27   23   1    37",
+        "279  8    279  12   3    127  5    4    6",
+        "8    129  1279 239  4    123  6    5    37",
+        "269  3    4    26   7    5    29   8    1",
+        "5    679  1279 369  12   8    239  27   4",
+        "239  129  129  4    5    13   7    6    8",
+        "67   167  8    127  12   9    4    3    5",
+        "37   4    5    37   8    6    1    9    2"
+    ]);
+    check(als::xz(&cands), Technique::AlsXz,
+          &[(36, 5)], &[]);
+}
+
+#[test]
+fn als_xy_wing() {
+    // ALS-XY-Wing in box 1, row 8, box 2: r8c3 <> 5
+    let cands = pencilmarks([
+        "47    6     235   79    58    89    235   1     2345",
+        "13    239   235   4     15    6     2359  7     8",
+        "1478  4589  158   157   2     3     6     49    45",
+        "5     37    6     8     9     17    4     23    123",
+        "2     478   378   15    6     1457  135   358   9",
+        "48    1     9     2     3     45    7     58    6",
+        "9     25    1235  135   4     15    8     6     7",
+        "6     3578  13578 1359  158   2     1359  34    1345",
+        "138   358   4     6     7     89    12359 2359  1235"
+    ]);
+    check(als::xy_wing(&cands), Technique::AlsXyWing,
+          &[(65, 4)], &[]);
+}
+
+#[test]
+fn death_blossom() {
+    // Death Blossom in box 7, column 9, box 1: r9c3 <> 5
+    let cands = pencilmarks([
+        "6    4    589  578  5789 5789 2    1    3",
+        "379  137  89   168  3689 2    5    4    67",
+        "357  135  2    14   3456 456  68   678  9",
+        "4    2    3    567  5679 5679 689  578  1",
+        "579  57   6    4578 1    5789 3    2    45",
+        "1    8    579  2    4679 3    69   567  4567",
+        "2    9    1    3    456  46   7    56   8",
+        "35   356  4    5678 5678 5678 1    9    2",
+        "8    567  57   9    2    1    4    3    56"
+    ]);
+    check(als::death_blossom(&cands), Technique::DeathBlossom,
+          &[(74, 4)], &[]);
+}
+
+#[test]
+fn sue_de_coq() {
+    // Sue de Coq in box 9, column 9: r5c9 <> 7 r5c9 <> 8 r5c9 <> 9 r9c7 <> 5
+    // r9c7 <> 6
+    let cands = pencilmarks([
+        "7      8      9      13     2      13     56     56     4",
+        "4      5      6      89     789    78     2      3      1",
+        "1      2      3      6      5      4      789    79     789",
+        "589    6      7      1489   89     18     3      459    2",
+        "589    49     1      4789   3      2      456789 45679  56789",
+        "2      3      48     479    6      5      4789   1      789",
+        "68     7      58     2      1      9      456    456    3",
+        "3      49     45     58     78     678    1      2      5679",
+        "69     1      2      35     4      367    5679   8      5679"
+    ]);
+    check(locked::sue_de_coq(&cands), Technique::SueDeCoq,
+          &[(44, 6), (44, 7), (44, 8), (78, 4), (78, 5)], &[]);
+}
+
+#[test]
+fn nishio_forcing_chain() {
+    // Nishio Forcing Chain: r1c2 <> 2
+    let cands = pencilmarks([
+        "6     24    5     89    89    7     1     3     24",
+        "3     1     28    6     5     4     27    9     278",
+        "789   4789  789   3     2     1     46    468   5",
+        "4     29    3     7     6     25    8     15    129",
+        "278   5     2678  149   19    28    2469  46    3",
+        "1     2689  2689  49    3     258   24569 7     269",
+        "29    2679  1     5     48    3     4679  468   679",
+        "578   678   678   2     148   9     3     15    14678",
+        "589   3     4     18    7     6     59    2     189"
+    ]);
+    check(forcing::nishio(&cands), Technique::Nishio,
+          &[(1, 1)], &[]);
+}
+
+#[test]
+fn cell_forcing_chain() {
+    // Cell Forcing Chain: r1c8 = 7 r1c9 = 8 r2c8 = 4 r6c1 = 6 r6c9 = 3
+    // r8c8 = 6 r8c9 = 7 r9c2 = 6
+    let cands = pencilmarks([
+        "2       1357    135     3567    4       1367    9       578     578",
+        "8       134579  13459   23579   1279    12379   6       2457    257",
+        "459     4579    6       8       279     279     3       1       257",
+        "3       12469   1249    2679    5       1246789 18      289     1289",
+        "1459    8       12459   239     129     12349   7       259     6",
+        "1569    12569   7       2369    1289    123689  4       2589    123589",
+        "1459    13459   134589  79      6       789     2       789     1789",
+        "169     1269    1289    4       3       5       18      6789    1789",
+        "7       269     289     1       289     289     5       3       4"
+    ]);
+    check(forcing::cell(&cands), Technique::CellForcingChain,
+          &[], &[(7, 6), (8, 7), (16, 3), (45, 5), (53, 2), (70, 5), (71, 6), (73, 5)]);
+}
+
+#[test]
+fn unit_forcing_chain() {
+    // Unit Forcing Chain in box 1: r1c8 = 7 r1c9 = 8 r2c8 = 4 r6c1 = 6
+    // r6c9 = 3 r8c8 = 6 r8c9 = 7 r9c2 = 6
+    let cands = pencilmarks([
+        "2       1357    135     3567    4       1367    9       578     578",
+        "8       134579  13459   23579   1279    12379   6       2457    257",
+        "459     4579    6       8       279     279     3       1       257",
+        "3       12469   1249    2679    5       1246789 18      289     1289",
+        "1459    8       12459   239     129     12349   7       259     6",
+        "1569    12569   7       2369    1289    123689  4       2589    123589",
+        "1459    13459   134589  79      6       789     2       789     1789",
+        "169     1269    1289    4       3       5       18      6789    1789",
+        "7       269     289     1       289     289     5       3       4"
+    ]);
+    check(forcing::unit(&cands), Technique::UnitForcingChain,
+          &[], &[(7, 6), (8, 7), (16, 3), (45, 5), (53, 2), (70, 5), (71, 6), (73, 5)]);
+}